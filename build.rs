@@ -0,0 +1,30 @@
+fn main() {
+    #[cfg(feature = "free")]
+    free();
+}
+
+/// Generates `free::Icon` from the vendored Font Awesome Free package.
+#[cfg(feature = "free")]
+fn free() {
+    const PACKAGE: &str = "assets/fontawesome-free";
+    const FILE: &str = "fa_free.rs";
+
+    let generator = floem_fontawesome_codegen::Generator::new(PACKAGE).crate_path("crate");
+    if generator.svgs_dir().join("solid").is_dir() {
+        generator.emit(FILE).unwrap();
+        return;
+    }
+
+    // Report the missing package where `free` is declared instead of panicking here.
+    println!("cargo:rerun-if-changed={PACKAGE}");
+    let message = format!(
+        "the `free` feature needs the vendored Font Awesome Free package in `{PACKAGE}`, \
+         which is missing from this checkout; `scripts/fetch-fontawesome-free.sh` vendors it"
+    );
+    let out_dir = std::env::var_os("OUT_DIR").unwrap();
+    std::fs::write(
+        std::path::Path::new(&out_dir).join(FILE),
+        format!("compile_error!({message:?});\n"),
+    )
+    .unwrap();
+}
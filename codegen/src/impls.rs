@@ -0,0 +1,142 @@
+//! The impls every icon enum gets, shared by `#[fa_icons]` and the [`Generator`].
+//!
+//! Both render the same source from an [`IconImpls`]; they only differ in how the tables
+//! are written. The macro passes placeholder identifiers for the enum, the crate path and
+//! the tables and swaps in tokens after parsing, since `$crate` can't be written as a
+//! string.
+//!
+//! [`Generator`]: crate::Generator
+
+/// Trait impls and tables of one icon enum.
+///
+/// Tables are comma separated Rust expressions with one entry per icon, in declaration
+/// order.
+pub struct IconImpls<'a> {
+    /// The enum's name.
+    pub name: &'a str,
+    /// Path of the `floem_fontawesome` crate.
+    pub krate: &'a str,
+    /// Font Awesome name of each icon, as string literals.
+    pub names: &'a str,
+    /// `Self::<Variant>` of each icon.
+    pub all: &'a str,
+    /// Svg table row of each icon, one column per `FaVariant::ALL` entry.
+    pub svgs: &'a str,
+    /// `Option<FaIconInfo>` of each icon, when the icon set has metadata.
+    pub infos: Option<&'a str>,
+    /// Whether every icon is a brand icon.
+    pub brands: bool,
+}
+
+impl IconImpls<'_> {
+    /// The impls' Rust source.
+    pub fn render(&self) -> String {
+        let IconImpls {
+            name,
+            krate,
+            names,
+            all,
+            svgs,
+            infos,
+            brands,
+        } = *self;
+
+        let mut methods = String::new();
+        if brands {
+            methods += "
+
+    fn is_brand(&self) -> bool {
+        true
+    }";
+        }
+        if let Some(infos) = infos {
+            methods += &format!(
+                "
+
+    fn info(&self) -> Option<&'static {krate}::FaIconInfo> {{
+        static INFOS: &[Option<{krate}::FaIconInfo>] = &[{infos}];
+        INFOS[*self as usize].as_ref()
+    }}"
+            );
+        }
+
+        format!(
+            "{krate}::__fa_serde!({name});
+
+impl floem::IntoView for {name} {{
+    type V = {krate}::FaIcon<{name}>;
+
+    fn into_view(self) -> Self::V {{
+        {krate}::fa_icon(self)
+    }}
+}}
+
+impl {name} {{
+    /// Every icon of the set, in declaration order.
+    pub const ALL: &'static [Self] = &[{all}];
+}}
+
+impl ::core::str::FromStr for {name} {{
+    type Err = {krate}::FaUnknownIconError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {{
+        {krate}::__fa_from_str(name, Self::ALL)
+    }}
+}}
+
+impl ::core::fmt::Display for {name} {{
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {{
+        f.write_str({krate}::FaIconTrait::name(self))
+    }}
+}}
+
+impl {krate}::FaIconTrait for {name} {{
+    fn name(&self) -> &'static str {{
+        static NAMES: &[&str] = &[{names}];
+        NAMES[*self as usize]
+    }}
+
+    fn try_svg(&self, variant: {krate}::FaVariant) -> Option<&'static str> {{
+        // One row per icon, one column per `FaVariant::ALL` entry
+        static SVGS: &[[Option<&str>; {krate}::FaVariant::ALL.len()]] = &[{svgs}];
+        SVGS[*self as usize][variant.index()?]
+    }}{methods}
+}}
+"
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let impls = IconImpls {
+            name: "Icon",
+            krate: "crate",
+            names: "\"house\",",
+            all: "Self::House,",
+            svgs: "[None],",
+            infos: None,
+            brands: false,
+        };
+        let source = impls.render();
+        assert!(source.starts_with("crate::__fa_serde!(Icon);\n"));
+        assert!(source.contains("impl crate::FaIconTrait for Icon {"));
+        assert!(source.contains("static NAMES: &[&str] = &[\"house\",];"));
+        assert!(!source.contains("fn is_brand"));
+        assert!(!source.contains("fn info"));
+
+        let source = IconImpls {
+            infos: Some("None,"),
+            brands: true,
+            ..impls
+        }
+        .render();
+        assert!(source.contains("fn is_brand(&self) -> bool {\n        true\n    }"));
+        assert!(source.contains("static INFOS: &[Option<crate::FaIconInfo>] = &[None,];"));
+        assert!(source.trim_end().ends_with("}\n}"));
+    }
+}
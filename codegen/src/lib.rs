@@ -0,0 +1,581 @@
+//! Build-time generator for `floem-fontawesome` icon enums.
+//!
+//! Rather than hand-listing every icon in `fa_icon_build!`, point a [`Generator`] at a
+//! Font Awesome package (or directly at its `svgs/` directory) from your `build.rs`:
+//!
+//! ```no_run
+//! floem_fontawesome_codegen::Generator::new("assets/fontawesome")
+//!     .enum_name("Icon")
+//!     .emit("fa_icons.rs")
+//!     .unwrap();
+//! ```
+//!
+//! and include the generated enum where it should live:
+//!
+//! ```ignore
+//! include!(concat!(env!("OUT_DIR"), "/fa_icons.rs"));
+//! ```
+//!
+//! The generated code implements `FaIconTrait` and `IntoView` with the same [`IconImpls`] as
+//! the macro.
+//!
+//! Icon sets that can't be committed, such as Font Awesome Pro, can be passed in through
+//! an environment variable instead, falling back to the bundled Free set when it's unset:
+//!
+//! ```no_run
+//! floem_fontawesome_codegen::Generator::from_env("FONTAWESOME_PRO_DIR")
+//!     .free_fallback()
+//!     .emit("fa_icons.rs")
+//!     .unwrap();
+//! ```
+
+mod impls;
+mod json;
+pub mod metadata;
+mod naming;
+
+pub use impls::IconImpls;
+pub use metadata::IconMetadata;
+pub use naming::{file_stem, variant_ident};
+
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::{self, Write as _};
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+use std::{env, fs, io};
+
+/// Family and style of every variant, in the order of `FaVariant::ALL`.
+///
+/// New Font Awesome families are appended here and to `FaVariant::ALL`, which keeps the
+/// columns of existing svg tables in place.
+pub const VARIANTS: &[(&str, &str)] = &[
+    ("classic", "solid"),
+    ("classic", "regular"),
+    ("classic", "light"),
+    ("classic", "thin"),
+    ("duotone", "solid"),
+    ("sharp", "solid"),
+    ("sharp", "regular"),
+    ("sharp", "light"),
+    ("sharp", "thin"),
+    ("sharp-duotone", "solid"),
+    ("sharp-duotone", "regular"),
+    ("sharp-duotone", "light"),
+    ("sharp-duotone", "thin"),
+    ("duotone", "regular"),
+    ("duotone", "light"),
+    ("duotone", "thin"),
+    ("classic", "brands"),
+    ("chisel", "regular"),
+    ("etch", "solid"),
+    ("jelly", "regular"),
+    ("jelly-duo", "regular"),
+    ("jelly-fill", "regular"),
+    ("notdog", "solid"),
+    ("notdog-duo", "solid"),
+    ("slab", "regular"),
+    ("slab-press", "regular"),
+    ("thumbprint", "light"),
+    ("whiteboard", "semibold"),
+    ("utility", "semibold"),
+    ("utility-duo", "semibold"),
+    ("utility-fill", "semibold"),
+];
+
+/// Style directories of a Font Awesome `svgs/` tree, in the order of `FaVariant::ALL`:
+/// [`variant_dir`] of each [`VARIANTS`] entry.
+pub static VARIANT_DIRS: LazyLock<Vec<String>> = LazyLock::new(|| {
+    VARIANTS
+        .iter()
+        .map(|(family, style)| variant_dir(family, style))
+        .collect()
+});
+
+/// Directory Font Awesome puts a family's style in: the bare style for the classic family,
+/// `duotone` for solid duotone and `<family>-<style>` otherwise.
+pub fn variant_dir(family: &str, style: &str) -> String {
+    match (family, style) {
+        ("classic", style) => style.to_string(),
+        ("duotone", "solid") => "duotone".to_string(),
+        (family, style) => format!("{family}-{style}"),
+    }
+}
+
+/// An icon found while scanning, together with the style directories it exists in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IconEntry {
+    /// Font Awesome name, which is also the file stem (`arrow-right`).
+    pub name: String,
+    /// Identifier of the generated enum variant (`ArrowRight`).
+    pub ident: String,
+    /// Style directories containing `<name>.svg`.
+    pub dirs: Vec<&'static str>,
+}
+
+/// Result of scanning a `svgs/` tree.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IconSet {
+    /// Icons that get an enum variant, sorted by name.
+    pub icons: Vec<IconEntry>,
+    /// Names that could not be turned into a unique identifier.
+    pub skipped: Vec<String>,
+}
+
+/// Generates an icon enum from a Font Awesome package directory.
+#[derive(Debug, Clone)]
+pub struct Generator {
+    path: PathBuf,
+    enum_name: String,
+    visibility: String,
+    crate_path: String,
+    brands_enum: Option<String>,
+    env_var: Option<String>,
+    free_fallback: bool,
+}
+
+impl Generator {
+    /// Creates a generator for a Font Awesome package root or its `svgs/` directory.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            enum_name: "Icon".to_string(),
+            visibility: "pub".to_string(),
+            crate_path: "::floem_fontawesome".to_string(),
+            brands_enum: None,
+            env_var: None,
+            free_fallback: false,
+        }
+    }
+
+    /// Creates a generator for the directory named by the environment variable `var`.
+    ///
+    /// [`Generator::emit`] asks cargo to rerun when the variable changes. When it isn't set,
+    /// the generated file is a compile error naming the variable, or a re-export of the
+    /// bundled Free icons with [`Generator::free_fallback`].
+    pub fn from_env(var: impl Into<String>) -> Self {
+        let var = var.into();
+        let path = env::var_os(&var).unwrap_or_default();
+        Self {
+            env_var: Some(var),
+            ..Self::new(path)
+        }
+    }
+
+    /// Re-exports `free::Icon` under the enum name when the environment variable given to
+    /// [`Generator::from_env`] is unset. Needs the `free` feature of `floem-fontawesome`.
+    pub fn free_fallback(mut self) -> Self {
+        self.free_fallback = true;
+        self
+    }
+
+    /// Name of the generated enum. Defaults to `Icon`.
+    pub fn enum_name(mut self, name: impl Into<String>) -> Self {
+        self.enum_name = name.into();
+        self
+    }
+
+    /// Visibility of the generated enum, e.g. `pub(crate)`. Defaults to `pub`.
+    pub fn visibility(mut self, visibility: impl Into<String>) -> Self {
+        self.visibility = visibility.into();
+        self
+    }
+
+    /// Path of the `floem-fontawesome` crate as seen from the generated code.
+    /// Defaults to `::floem_fontawesome`.
+    pub fn crate_path(mut self, path: impl Into<String>) -> Self {
+        self.crate_path = path.into();
+        self
+    }
+
+    /// Puts brand icons into a separate enum with this name.
+    ///
+    /// Brand icons only exist in the `brands` style, so keeping them apart means a brand
+    /// can't be asked for in a style it doesn't have. Icons that exist both as a brand and
+    /// in other styles get a variant in each enum.
+    pub fn brands_enum(mut self, name: impl Into<String>) -> Self {
+        self.brands_enum = Some(name.into());
+        self
+    }
+
+    /// The `svgs/` directory that gets scanned.
+    pub fn svgs_dir(&self) -> PathBuf {
+        let nested = self.path.join("svgs");
+        if nested.is_dir() {
+            nested
+        } else {
+            self.path.clone()
+        }
+    }
+
+    /// Scans the `svgs/` directory for icons.
+    pub fn scan(&self) -> io::Result<IconSet> {
+        if let Some(var) = self.missing_env_var() {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("environment variable `{var}` is not set"),
+            ));
+        }
+        let dir = self.svgs_dir();
+        if !dir.is_dir() {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("Font Awesome svgs directory not found: {}", dir.display()),
+            ));
+        }
+
+        let mut found: BTreeMap<String, Vec<&'static str>> = BTreeMap::new();
+        for style in VARIANT_DIRS.iter().map(String::as_str) {
+            let style_dir = dir.join(style);
+            if !style_dir.is_dir() {
+                continue;
+            }
+            for entry in fs::read_dir(&style_dir)? {
+                let path = entry?.path();
+                if path.extension().is_none_or(|ext| ext != "svg") {
+                    continue;
+                }
+                if let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) {
+                    found.entry(stem.to_string()).or_default().push(style);
+                }
+            }
+        }
+
+        let mut set = IconSet::default();
+        let mut idents = HashSet::new();
+        for (name, dirs) in found {
+            match variant_ident(&name) {
+                Some(ident) if idents.insert(ident.clone()) => {
+                    set.icons.push(IconEntry { name, ident, dirs })
+                }
+                _ => set.skipped.push(name),
+            }
+        }
+        Ok(set)
+    }
+
+    /// Scans the icon directory and returns the generated Rust source.
+    pub fn generate(&self) -> io::Result<String> {
+        let set = self.scan()?;
+        self.render(&set)
+    }
+
+    /// Generates the enum into `$OUT_DIR/<file_name>`.
+    ///
+    /// Meant to be called from a build script: it asks cargo to rerun when the icon
+    /// directory or the environment variable given to [`Generator::from_env`] changes, and
+    /// warns about icons that had to be skipped.
+    pub fn emit(&self, file_name: impl AsRef<Path>) -> io::Result<()> {
+        let out_dir = env::var_os("OUT_DIR").ok_or_else(|| {
+            io::Error::other("OUT_DIR is not set, `emit` must run in a build script")
+        })?;
+        let out = Path::new(&out_dir).join(file_name);
+        if let Some(var) = &self.env_var {
+            println!("cargo:rerun-if-env-changed={var}");
+        }
+        if let Some(var) = self.missing_env_var() {
+            return fs::write(out, self.render_missing(var));
+        }
+        println!("cargo:rerun-if-changed={}", self.svgs_dir().display());
+        for file in metadata::metadata_files(&metadata::metadata_dir(&self.svgs_dir())) {
+            println!("cargo:rerun-if-changed={}", file.display());
+        }
+
+        let set = self.scan()?;
+        for name in &set.skipped {
+            println!("cargo:warning=skipping Font Awesome icon `{name}`: no valid identifier");
+        }
+        fs::write(out, self.render(&set)?)
+    }
+
+    fn missing_env_var(&self) -> Option<&str> {
+        self.env_var
+            .as_deref()
+            .filter(|var| env::var_os(var).is_none())
+    }
+
+    /// Source standing in for the enum when the icon directory's environment variable is
+    /// unset.
+    fn render_missing(&self, var: &str) -> String {
+        let Self {
+            visibility,
+            crate_path: krate,
+            enum_name,
+            ..
+        } = self;
+        if !self.free_fallback {
+            let message = format!(
+                "environment variable `{var}` is not set, point it at a Font Awesome package"
+            );
+            return format!("compile_error!({message:?});\n");
+        }
+        let mut out = format!("{visibility} use {krate}::free::Icon as {enum_name};\n");
+        if let Some(brands_enum) = &self.brands_enum {
+            out += &format!("{visibility} use {krate}::free::Icon as {brands_enum};\n");
+        }
+        out
+    }
+
+    fn render(&self, set: &IconSet) -> io::Result<String> {
+        // The generated file lives in OUT_DIR, so `include_str!` needs absolute paths.
+        let dir = std::path::absolute(self.svgs_dir())?;
+        let metadata = metadata::load(&metadata::metadata_dir(&dir))?;
+        let mut out = String::new();
+        Source {
+            generator: self,
+            dir: &dir,
+            metadata: &metadata,
+        }
+        .write(&mut out, set)
+        .expect("writing to a String cannot fail");
+        Ok(out)
+    }
+}
+
+/// What the generated source is written from.
+struct Source<'a> {
+    generator: &'a Generator,
+    /// Absolute `svgs/` directory.
+    dir: &'a Path,
+    metadata: &'a BTreeMap<String, IconMetadata>,
+}
+
+impl Source<'_> {
+    fn write(&self, out: &mut String, set: &IconSet) -> fmt::Result {
+        let Generator {
+            enum_name,
+            brands_enum,
+            ..
+        } = self.generator;
+        let Some(brands_enum) = brands_enum else {
+            return self.write_enum(out, enum_name, &set.icons, false);
+        };
+
+        let mut icons = Vec::new();
+        let mut brands = Vec::new();
+        for icon in &set.icons {
+            let (brand_dirs, dirs): (Vec<_>, Vec<_>) =
+                icon.dirs.iter().partition(|dir| **dir == "brands");
+            if !dirs.is_empty() {
+                icons.push(IconEntry {
+                    dirs,
+                    ..icon.clone()
+                });
+            }
+            if !brand_dirs.is_empty() {
+                brands.push(IconEntry {
+                    dirs: brand_dirs,
+                    ..icon.clone()
+                });
+            }
+        }
+        self.write_enum(out, enum_name, &icons, false)?;
+        writeln!(out)?;
+        self.write_enum(out, brands_enum, &brands, true)
+    }
+
+    fn write_enum(
+        &self,
+        out: &mut String,
+        name: &str,
+        icons: &[IconEntry],
+        brands: bool,
+    ) -> fmt::Result {
+        let Generator {
+            visibility,
+            crate_path: krate,
+            ..
+        } = self.generator;
+        let dir = self.dir;
+
+        writeln!(
+            out,
+            "#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]"
+        )?;
+        writeln!(out, "#[allow(dead_code)]")?;
+        writeln!(out, "{visibility} enum {name} {{")?;
+        for icon in icons {
+            writeln!(out, "    {},", icon.ident)?;
+        }
+        writeln!(out, "}}")?;
+        writeln!(out)?;
+
+        let mut names = String::new();
+        let mut all = String::new();
+        let mut svgs = String::new();
+        for icon in icons {
+            write!(names, "\n            {:?},", icon.name)?;
+            write!(all, "\n        Self::{},", icon.ident)?;
+            write!(svgs, "\n            // {}\n            [", icon.name)?;
+            for (index, style) in VARIANT_DIRS.iter().enumerate() {
+                if icon.dirs.contains(&style.as_str()) {
+                    let file = dir.join(style).join(format!("{}.svg", icon.name));
+                    write!(
+                        svgs,
+                        "\n                {krate}::__fa_embed({index}, include_str!({:?})),",
+                        file.display().to_string()
+                    )?;
+                } else {
+                    write!(svgs, "\n                None,")?;
+                }
+            }
+            write!(svgs, "\n            ],")?;
+        }
+        let infos = (!self.metadata.is_empty()).then(|| {
+            let mut infos = String::new();
+            for icon in icons {
+                match self.metadata.get(&icon.name) {
+                    Some(metadata) => {
+                        infos += &format!(
+                            "\n            Some({}),",
+                            metadata.info_expr(&icon.name, krate)
+                        )
+                    }
+                    None => infos += "\n            None,",
+                }
+            }
+            infos + "\n        "
+        });
+        out.push_str(
+            &IconImpls {
+                name,
+                krate,
+                names: &(names + "\n        "),
+                all: &(all + "\n    "),
+                svgs: &(svgs + "\n        "),
+                infos: infos.as_deref(),
+                brands,
+            }
+            .render(),
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture(name: &str, files: &[&str]) -> PathBuf {
+        let root = env::temp_dir().join(format!("fa-codegen-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        for file in files {
+            let path = root.join("svgs").join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "<svg></svg>").unwrap();
+        }
+        root
+    }
+
+    #[test]
+    fn test_variant_dirs() {
+        assert_eq!(VARIANT_DIRS[4], "duotone");
+        assert_eq!(VARIANT_DIRS[16], "brands");
+        assert_eq!(VARIANT_DIRS[20], "jelly-duo-regular");
+        let unique: HashSet<_> = VARIANT_DIRS.iter().collect();
+        assert_eq!(unique.len(), VARIANTS.len());
+    }
+
+    #[test]
+    fn test_scan() {
+        let root = fixture(
+            "scan",
+            &[
+                "solid/arrow-right.svg",
+                "regular/arrow-right.svg",
+                "brands/github.svg",
+                "solid/42-group.svg",
+                "solid/font.awesome.svg",
+                "solid/notes.txt",
+            ],
+        );
+        let set = Generator::new(&root).scan().unwrap();
+        assert_eq!(
+            set.icons,
+            vec![
+                IconEntry {
+                    name: "42-group".into(),
+                    ident: "N42Group".into(),
+                    dirs: vec!["solid"],
+                },
+                IconEntry {
+                    name: "arrow-right".into(),
+                    ident: "ArrowRight".into(),
+                    dirs: vec!["solid", "regular"],
+                },
+                IconEntry {
+                    name: "github".into(),
+                    ident: "Github".into(),
+                    dirs: vec!["brands"],
+                },
+            ]
+        );
+        assert_eq!(set.skipped, vec!["font.awesome".to_string()]);
+
+        let source = Generator::new(&root)
+            .enum_name("MyIcon")
+            .generate()
+            .unwrap();
+        assert!(source.contains("pub enum MyIcon {"));
+        assert!(source.contains("            \"42-group\",\n"));
+        assert!(source.contains("SVGS[*self as usize][variant.index()?]"));
+        let github = source.split_once("// github\n").unwrap().1;
+        let row: Vec<_> = github.lines().skip(1).take(VARIANTS.len()).collect();
+        assert!(row[..16].iter().all(|cell| cell.trim() == "None,"));
+        assert!(row[16].contains("::floem_fontawesome::__fa_embed(16, include_str!("));
+        assert!(row[16].contains("brands/github.svg"));
+
+        let source = Generator::new(&root)
+            .brands_enum("Brand")
+            .generate()
+            .unwrap();
+        let (icons, brands) = source.split_once("pub enum Brand {").unwrap();
+        assert!(icons.contains("    ArrowRight,"));
+        assert!(!icons.contains("Github"));
+        assert!(brands.contains("    Github,"));
+        assert!(brands.contains("fn is_brand(&self) -> bool"));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_info() {
+        let root = fixture("info", &["solid/house.svg", "solid/custom.svg"]);
+        let source = Generator::new(&root).generate().unwrap();
+        assert!(!source.contains("fn info"));
+
+        fs::create_dir_all(root.join("metadata")).unwrap();
+        fs::write(
+            root.join("metadata/icons.json"),
+            r#"{"house": {"label": "House", "unicode": "f015", "styles": ["solid"]}}"#,
+        )
+        .unwrap();
+        let source = Generator::new(&root).generate().unwrap();
+        let (_, infos) = source.split_once("static INFOS").unwrap();
+        let mut rows = infos.lines().skip(1);
+        assert_eq!(rows.next().unwrap().trim(), "None,");
+        let house = rows.next().unwrap();
+        assert!(house.contains("label: \"House\""));
+        assert!(house.contains("unicode: Some('\\u{f015}')"));
+        assert!(house.contains("styles: &[::floem_fontawesome::FaVariant::ALL[0], ]"));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_missing_env_var() {
+        let generator = Generator::from_env("FLOEM_FONTAWESOME_TEST_UNSET_DIR");
+        assert!(generator.scan().is_err());
+        assert!(
+            generator
+                .render_missing("FLOEM_FONTAWESOME_TEST_UNSET_DIR")
+                .starts_with(
+                    "compile_error!(\"environment variable `FLOEM_FONTAWESOME_TEST_UNSET_DIR`"
+                )
+        );
+        assert_eq!(
+            generator
+                .enum_name("ProIcon")
+                .free_fallback()
+                .render_missing("FLOEM_FONTAWESOME_TEST_UNSET_DIR"),
+            "pub use ::floem_fontawesome::free::Icon as ProIcon;\n"
+        );
+    }
+}
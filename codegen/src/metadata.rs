@@ -0,0 +1,315 @@
+//! Icon metadata from a Font Awesome package's `metadata/` directory.
+//!
+//! `icon-families.json` is read when present (Font Awesome 6.4 and later), `icons.json`
+//! otherwise, together with `categories.yml`. Packages without metadata simply yield no
+//! entries.
+//!
+//! Both formats are read with the crate's own parsers rather than serde, see the `json`
+//! module for why.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+
+use crate::json::{self, Value};
+use crate::{VARIANT_DIRS, variant_dir};
+
+/// What Font Awesome knows about an icon.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IconMetadata {
+    /// Human readable name (`Arrow Right`).
+    pub label: String,
+    /// Code point in the Font Awesome fonts.
+    pub unicode: Option<char>,
+    pub search_terms: Vec<String>,
+    /// Former names of the icon.
+    pub aliases: Vec<String>,
+    /// Labels of the categories the icon is listed in.
+    pub categories: Vec<String>,
+    /// Style directories Font Awesome ships the icon in, in the order of `VARIANT_DIRS`.
+    pub styles: Vec<&'static str>,
+    /// Font Awesome version that introduced the icon.
+    pub version_added: Option<String>,
+}
+
+impl IconMetadata {
+    /// Renders an `FaIconInfo` expression, with `krate` as the path of `floem-fontawesome`.
+    pub fn info_expr(&self, name: &str, krate: &str) -> String {
+        let mut expr = format!(
+            "{krate}::FaIconInfo {{ name: {name:?}, label: {:?}, ",
+            self.label
+        );
+        let unicode = match self.unicode {
+            Some(c) => format!("Some('\\u{{{:x}}}')", c as u32),
+            None => "None".to_string(),
+        };
+        write!(expr, "unicode: {unicode}, ").unwrap();
+        write!(expr, "search_terms: &{:?}, ", self.search_terms).unwrap();
+        write!(expr, "aliases: &{:?}, ", self.aliases).unwrap();
+        write!(expr, "categories: &{:?}, ", self.categories).unwrap();
+        expr.push_str("styles: &[");
+        for style in &self.styles {
+            let index = VARIANT_DIRS.iter().position(|dir| dir == style).unwrap();
+            write!(expr, "{krate}::FaVariant::ALL[{index}], ").unwrap();
+        }
+        write!(expr, "], version_added: {:?} }}", self.version_added).unwrap();
+        expr
+    }
+}
+
+/// The `metadata/` directory next to a package's `svgs/` directory.
+pub fn metadata_dir(svgs_dir: &Path) -> PathBuf {
+    svgs_dir.parent().unwrap_or(Path::new("")).join("metadata")
+}
+
+/// The metadata files [`load`] reads from `dir`, for change tracking.
+pub fn metadata_files(dir: &Path) -> Vec<PathBuf> {
+    let icons = ["icon-families.json", "icons.json"]
+        .into_iter()
+        .map(|file| dir.join(file))
+        .find(|file| file.is_file());
+    icons
+        .into_iter()
+        .chain(Some(dir.join("categories.yml")).filter(|file| file.is_file()))
+        .collect()
+}
+
+/// Loads the metadata of every icon in `dir`, keyed by Font Awesome name.
+pub fn load(dir: &Path) -> io::Result<BTreeMap<String, IconMetadata>> {
+    let mut icons = BTreeMap::new();
+    let (file, families) = if dir.join("icon-families.json").is_file() {
+        (dir.join("icon-families.json"), true)
+    } else if dir.join("icons.json").is_file() {
+        (dir.join("icons.json"), false)
+    } else {
+        return Ok(icons);
+    };
+
+    let text = fs::read_to_string(&file)?;
+    let root = json::parse(&text).map_err(|error| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{}: {error}", file.display()),
+        )
+    })?;
+    let Value::Object(entries) = root else {
+        return Ok(icons);
+    };
+    for (name, entry) in entries {
+        let styles = if families {
+            family_styles(&entry)
+        } else {
+            entry.get("styles").map(Value::strings).unwrap_or_default()
+        };
+        let styles = VARIANT_DIRS
+            .iter()
+            .map(String::as_str)
+            .filter(|dir| styles.iter().any(|style| style == dir))
+            .collect();
+
+        let unicode = entry
+            .get("unicode")
+            .and_then(Value::as_str)
+            .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+            .and_then(char::from_u32);
+        let metadata = IconMetadata {
+            label: entry
+                .get("label")
+                .and_then(Value::as_str)
+                .unwrap_or(&name)
+                .to_string(),
+            unicode,
+            search_terms: entry
+                .get("search")
+                .and_then(|search| search.get("terms"))
+                .map(Value::strings)
+                .unwrap_or_default(),
+            aliases: entry
+                .get("aliases")
+                .and_then(|aliases| aliases.get("names"))
+                .map(Value::strings)
+                .unwrap_or_default(),
+            categories: Vec::new(),
+            styles,
+            version_added: entry
+                .get("changes")
+                .and_then(|changes| changes.items().first())
+                .and_then(Value::as_str)
+                .map(str::to_string),
+        };
+        icons.insert(name, metadata);
+    }
+
+    let categories = dir.join("categories.yml");
+    if categories.is_file() {
+        for (icon, label) in parse_categories(&fs::read_to_string(categories)?) {
+            if let Some(metadata) = icons.get_mut(&icon) {
+                metadata.categories.push(label);
+            }
+        }
+    }
+    Ok(icons)
+}
+
+/// Style directories listed in an `icon-families.json` entry, under any license.
+fn family_styles(entry: &Value) -> Vec<String> {
+    let Some(Value::Object(licenses)) = entry.get("familyStylesByLicense") else {
+        return Vec::new();
+    };
+    licenses
+        .values()
+        .flat_map(Value::items)
+        .filter_map(|pair| {
+            let family = pair.get("family")?.as_str()?;
+            let style = pair.get("style")?.as_str()?;
+            Some(variant_dir(family, style))
+        })
+        .collect()
+}
+
+/// Reads `categories.yml` into `(icon, category label)` pairs.
+///
+/// Only the shape Font Awesome uses is supported: a top-level key per category holding an
+/// `icons` list and a `label`.
+fn parse_categories(text: &str) -> Vec<(String, String)> {
+    let mut pairs = Vec::new();
+    let mut icons = Vec::new();
+    let mut label = None;
+    let mut in_icons = false;
+    let mut flush = |icons: &mut Vec<String>, label: &mut Option<String>| {
+        if let Some(label) = label.take() {
+            pairs.extend(icons.drain(..).map(|icon| (icon, label.clone())));
+        }
+        icons.clear();
+    };
+
+    for line in text.lines() {
+        if line.trim().is_empty() || line.trim_start().starts_with('#') {
+            continue;
+        }
+        let indented = line.starts_with([' ', '\t']);
+        let line = line.trim();
+        if !indented {
+            flush(&mut icons, &mut label);
+            in_icons = false;
+        } else if let Some(item) = line.strip_prefix("- ") {
+            if in_icons {
+                icons.push(unquote(item).to_string());
+            }
+        } else if let Some((key, value)) = line.split_once(':') {
+            in_icons = key == "icons";
+            if key == "label" {
+                label = Some(unquote(value.trim()).to_string());
+            }
+        }
+    }
+    flush(&mut icons, &mut label);
+    pairs
+}
+
+fn unquote(value: &str) -> &str {
+    value
+        .strip_prefix(['"', '\''])
+        .and_then(|value| value.strip_suffix(['"', '\'']))
+        .unwrap_or(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_categories() {
+        let pairs = parse_categories(
+            "accessibility:\n  icons:\n    - accessible-icon\n    - house\n  label: Accessibility\n\
+             buildings:\n  icons:\n    - house\n  label: \"Buildings\"\n",
+        );
+        assert_eq!(
+            pairs,
+            [
+                ("accessible-icon".into(), "Accessibility".into()),
+                ("house".into(), "Accessibility".into()),
+                ("house".into(), "Buildings".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_load_large() {
+        // Shaped like Font Awesome Pro's icon-families.json, which is tens of megabytes
+        // mostly because of the raw svgs of every family and style
+        let path = "M256 0C114.6 0 0 114.6 0 256S114.6 512 256 512s256-114.6 256-256S397.4 0 \
+                    256 0zM232 120c0-13.3 10.7-24 24-24s24 10.7 24 24V256c0 13.3-10.7 24-24 24s-24\
+                    -10.7-24-24V120z";
+        let svg = format!(
+            r#"{{"raw": "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 512 512\"><path d=\"{path}\"/></svg>", "viewBox": [0, 0, 512, 512], "width": 512, "height": 512, "path": "{path}"}}"#
+        );
+        let mut pairs = Vec::new();
+        let mut families: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+        for (family, style) in crate::VARIANTS {
+            pairs.push(format!(r#"{{"family": "{family}", "style": "{style}"}}"#));
+            families
+                .entry(family)
+                .or_default()
+                .push(format!(r#""{style}": {svg}"#));
+        }
+        let svgs = families
+            .iter()
+            .map(|(family, styles)| format!(r#""{family}": {{{}}}"#, styles.join(", ")))
+            .collect::<Vec<_>>();
+        let mut text = String::from("{");
+        for index in 0..3000 {
+            if index > 0 {
+                text.push(',');
+            }
+            write!(
+                text,
+                r#"
+                "icon-{index}": {{
+                    "changes": ["5.0.0", "6.5.0"],
+                    "label": "Icon \u00e9 {index}",
+                    "search": {{"terms": ["term", "caf\u00e9", "\ud83c\udfe0"]}},
+                    "unicode": "{:x}",
+                    "familyStylesByLicense": {{"free": [], "pro": [{}]}},
+                    "svgs": {{{}}}
+                }}"#,
+                0xe000 + index,
+                pairs.join(", "),
+                svgs.join(", ")
+            )
+            .unwrap();
+        }
+        text.push('}');
+        assert!(text.len() > 20_000_000);
+
+        let dir = std::env::temp_dir().join(format!("fa-metadata-large-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("icon-families.json"), &text).unwrap();
+        let icons = load(&dir).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(icons.len(), 3000);
+        let icon = &icons["icon-2999"];
+        assert_eq!(icon.label, "Icon é 2999");
+        assert_eq!(icon.unicode, char::from_u32(0xe000 + 2999));
+        assert_eq!(icon.search_terms, ["term", "café", "🏠"]);
+        assert_eq!(icon.styles, *VARIANT_DIRS);
+        assert_eq!(icon.version_added.as_deref(), Some("5.0.0"));
+    }
+
+    #[test]
+    fn test_family_styles() {
+        let entry = json::parse(
+            r#"{"familyStylesByLicense": {
+                "free": [{"family": "classic", "style": "solid"}],
+                "pro": [{"family": "duotone", "style": "solid"},
+                        {"family": "sharp-duotone", "style": "thin"}]}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            family_styles(&entry),
+            ["solid", "duotone", "sharp-duotone-thin"]
+        );
+    }
+}
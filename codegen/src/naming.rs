@@ -0,0 +1,135 @@
+//! Mapping between Font Awesome icon names and enum variant identifiers.
+//!
+//! Names are converted to PascalCase (`arrow-right` becomes `ArrowRight`), with a few
+//! manglings for names that can't be used as identifiers as-is:
+//!
+//! | Font Awesome name          | Variant      | Rule                                  |
+//! |----------------------------|--------------|---------------------------------------|
+//! | `0` ... `9`                | `Digit0`     | single digits get a `Digit` prefix    |
+//! | `42-group`, `00`, `500px`  | `N42Group`   | other leading digits get an `N` prefix |
+//! | `type`, `box`, `self`      | `Type_`      | Rust keywords get a trailing `_`      |
+//!
+//! [`file_stem`] reverses all of these, so mangled identifiers can be written directly in
+//! `fa_icon_build!` without a path override.
+
+/// Strict and reserved Rust keywords.
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl",
+    "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "static", "struct", "super", "trait", "true", "try", "type", "typeof",
+    "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+/// Converts a Font Awesome icon name into the identifier used for its enum variant.
+///
+/// `arrow-right` becomes `ArrowRight`, `0` becomes `Digit0`, `42-group` becomes `N42Group`
+/// and `type` becomes `Type_`. Returns `None` when the name contains characters that can't
+/// appear in an identifier.
+pub fn variant_ident(name: &str) -> Option<String> {
+    if !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+        return None;
+    }
+    let pascal: String = name.split('-').map(capitalize).collect();
+    let first = pascal.chars().next()?;
+
+    let ident = if first.is_ascii_digit() {
+        if pascal.len() == 1 {
+            format!("Digit{pascal}")
+        } else {
+            format!("N{pascal}")
+        }
+    } else if KEYWORDS.contains(&name) {
+        format!("{pascal}_")
+    } else {
+        pascal
+    };
+    Some(ident)
+}
+
+/// Converts an enum variant identifier back into a Font Awesome file stem.
+///
+/// `ArrowRight` becomes `arrow-right`: a hyphen goes before every uppercase letter but the
+/// first. The manglings applied by [`variant_ident`] are undone first. Digits stay attached
+/// to whatever precedes them, so names such as `arrow-down-1-9` can't be derived and need
+/// an explicit `= "path"` override in `fa_icon_build!`.
+pub fn file_stem(ident: &str) -> String {
+    let ident = ident.strip_prefix("r#").unwrap_or(ident);
+    let ident = ident.strip_suffix('_').unwrap_or(ident);
+
+    if let Some(digit) = ident.strip_prefix("Digit")
+        && digit.len() == 1
+        && digit.as_bytes()[0].is_ascii_digit()
+    {
+        return digit.to_string();
+    }
+    let ident = match ident.strip_prefix('N') {
+        Some(rest) if rest.starts_with(|c: char| c.is_ascii_digit()) => rest,
+        _ => ident,
+    };
+
+    let mut stem = String::with_capacity(ident.len() + 4);
+    for (i, c) in ident.char_indices() {
+        if i > 0 && c.is_ascii_uppercase() {
+            stem.push('-');
+        }
+        stem.push(c.to_ascii_lowercase());
+    }
+    stem
+}
+
+fn capitalize(segment: &str) -> String {
+    let mut chars = segment.chars();
+    match chars.next() {
+        Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_variant_ident() {
+        assert_eq!(variant_ident("house").as_deref(), Some("House"));
+        assert_eq!(variant_ident("arrow-right").as_deref(), Some("ArrowRight"));
+        assert_eq!(
+            variant_ident("arrow-down-1-9").as_deref(),
+            Some("ArrowDown19")
+        );
+        assert_eq!(variant_ident("h1").as_deref(), Some("H1"));
+        assert_eq!(variant_ident("0").as_deref(), Some("Digit0"));
+        assert_eq!(variant_ident("00").as_deref(), Some("N00"));
+        assert_eq!(variant_ident("100").as_deref(), Some("N100"));
+        assert_eq!(variant_ident("42-group").as_deref(), Some("N42Group"));
+        assert_eq!(variant_ident("500px").as_deref(), Some("N500px"));
+        assert_eq!(variant_ident("type").as_deref(), Some("Type_"));
+        assert_eq!(variant_ident("box").as_deref(), Some("Box_"));
+        assert_eq!(variant_ident("self").as_deref(), Some("Self_"));
+        assert_eq!(variant_ident("n").as_deref(), Some("N"));
+        assert_eq!(variant_ident("font.awesome"), None);
+        assert_eq!(variant_ident(""), None);
+    }
+
+    #[test]
+    fn test_file_stem() {
+        assert_eq!(file_stem("House"), "house");
+        assert_eq!(file_stem("ArrowRight"), "arrow-right");
+        assert_eq!(file_stem("FaceGrinBeamSweat"), "face-grin-beam-sweat");
+        assert_eq!(file_stem("H1"), "h1");
+        assert_eq!(file_stem("arrow_right"), "arrow_right");
+        assert_eq!(file_stem("r#type"), "type");
+        assert_eq!(file_stem("N"), "n");
+    }
+
+    #[test]
+    fn test_mangling_round_trip() {
+        for name in [
+            "0", "7", "00", "100", "42-group", "500px", "type", "box", "self", "n", "house",
+        ] {
+            let ident = variant_ident(name).unwrap();
+            assert_eq!(file_stem(&ident), name, "{ident}");
+        }
+    }
+}
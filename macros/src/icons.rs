@@ -0,0 +1,422 @@
+//! Expansion of the `#[fa_icons]` attribute.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use floem_fontawesome_codegen::metadata::{self, IconMetadata};
+use floem_fontawesome_codegen::{IconImpls, VARIANT_DIRS, VARIANTS, file_stem};
+use proc_macro::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+
+use crate::path::{self, Error, flatten_none_group, split_args};
+
+struct Args {
+    base: PathBuf,
+    krate: TokenStream,
+    require: Vec<&'static str>,
+    /// Whether to read the icon set's metadata for `FaIconTrait::info`.
+    metadata: bool,
+    /// Environment variables the path was read from.
+    env_vars: Vec<String>,
+}
+
+struct Variant {
+    attrs: Vec<TokenTree>,
+    ident: Ident,
+    stem: String,
+}
+
+struct IconEnum {
+    attrs: Vec<TokenTree>,
+    vis: Vec<TokenTree>,
+    enum_token: Ident,
+    name: Ident,
+    brace_span: Span,
+    variants: Vec<Variant>,
+}
+
+/// Expands the attribute.
+///
+/// The enum and its impls are emitted even when some icons fail to resolve, so that an
+/// error is reported once per missing icon instead of at every use of the enum.
+pub(crate) fn expand(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let (args, icons) = match (parse_args(attr), parse_enum(item)) {
+        (Ok(args), Ok(icons)) => (args, icons),
+        (Err(error), _) | (_, Err(error)) => return error.into_compile_error(),
+    };
+
+    let mut errors = Vec::new();
+    let mut names = TokenStream::new();
+    let mut all = TokenStream::new();
+    let mut svgs = TokenStream::new();
+    for variant in &icons.variants {
+        let mut literal = Literal::string(&variant.stem);
+        literal.set_span(variant.ident.span());
+        names.extend([TokenTree::Literal(literal), punct(',')]);
+        all.extend([
+            TokenTree::Ident(Ident::new("Self", Span::call_site())),
+            TokenTree::Punct(Punct::new(':', Spacing::Joint)),
+            TokenTree::Punct(Punct::new(':', Spacing::Alone)),
+            TokenTree::Ident(variant.ident.clone()),
+            punct(','),
+        ]);
+        let row = svg_row(&args, variant).unwrap_or_else(|error| {
+            errors.push(error);
+            row(&args.krate, &[None; VARIANTS.len()])
+        });
+        svgs.extend([row, punct(',')]);
+    }
+    // Pro's metadata is tens of megabytes, so it's only parsed when asked for
+    let infos = if args.metadata {
+        match metadata::load(&metadata::metadata_dir(&args.base)) {
+            Ok(metadata) => infos(&args.krate, &metadata, &icons.variants),
+            Err(error) => {
+                errors.push(Error::new(error.to_string(), Span::call_site()));
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    let mut out = TokenStream::new();
+    // Icon sets usually list icons a crate never uses
+    out.extend(
+        "#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)] #[allow(dead_code)]"
+            .parse::<TokenStream>()
+            .unwrap(),
+    );
+    out.extend(icons.attrs);
+    out.extend(icons.vis);
+    out.extend([
+        TokenTree::Ident(icons.enum_token),
+        TokenTree::Ident(icons.name.clone()),
+    ]);
+    let mut body = TokenStream::new();
+    for variant in icons.variants {
+        body.extend(variant.attrs);
+        body.extend([TokenTree::Ident(variant.ident), punct(',')]);
+    }
+    let mut body = Group::new(Delimiter::Brace, body);
+    body.set_span(icons.brace_span);
+    out.extend([TokenTree::Group(body)]);
+
+    out.extend(errors.into_iter().map(Error::into_compile_error));
+    // Reading the variables in the expansion makes cargo rebuild when they change.
+    for var in &args.env_vars {
+        let track = format!("const _: Option<&str> = ::core::option_env!({var:?});");
+        out.extend(track.parse::<TokenStream>().unwrap());
+    }
+    let impls = IconImpls {
+        name: "__Name",
+        krate: "__krate",
+        names: "__names",
+        all: "__all",
+        svgs: "__svgs",
+        infos: infos.is_some().then_some("__infos"),
+        brands: false,
+    };
+    out.extend(template(
+        &impls.render(),
+        &[
+            ("__Name", &TokenTree::Ident(icons.name).into()),
+            ("__krate", &args.krate),
+            ("__names", &names),
+            ("__all", &all),
+            ("__svgs", &svgs),
+            ("__infos", &infos.unwrap_or_default()),
+        ],
+    ));
+    out
+}
+
+/// The table behind `FaIconTrait::info`, when the icon set has metadata.
+fn infos(
+    krate: &TokenStream,
+    metadata: &BTreeMap<String, IconMetadata>,
+    variants: &[Variant],
+) -> Option<TokenStream> {
+    if metadata.is_empty() {
+        return None;
+    }
+    let mut infos = String::new();
+    for variant in variants {
+        match metadata.get(&variant.stem) {
+            Some(metadata) => {
+                infos += &format!("Some({}),", metadata.info_expr(&variant.stem, "__krate"))
+            }
+            None => infos += "None,",
+        }
+    }
+    Some(template(&infos, &[("__krate", krate)]))
+}
+
+/// Builds the svg table row of one icon, in the order of `VARIANT_DIRS`.
+fn svg_row(args: &Args, variant: &Variant) -> Result<TokenTree, Error> {
+    let span = variant.ident.span();
+    let files: Vec<_> = VARIANT_DIRS
+        .iter()
+        .map(|dir| {
+            let file = args.base.join(dir).join(format!("{}.svg", variant.stem));
+            file.is_file().then_some(file)
+        })
+        .collect();
+
+    for dir in &args.require {
+        let index = VARIANT_DIRS.iter().position(|d| d == dir).unwrap();
+        if files[index].is_none() {
+            let file = args.base.join(dir).join(format!("{}.svg", variant.stem));
+            return Err(Error::new(
+                format!(
+                    "icon `{}` has no `{dir}` svg: `{}` does not exist",
+                    variant.ident,
+                    file.display()
+                ),
+                span,
+            ));
+        }
+    }
+    if files.iter().all(Option::is_none) {
+        return Err(Error::new(
+            format!(
+                "icon `{}` not found: no style directory of `{}` contains `{}.svg`",
+                variant.ident,
+                args.base.display(),
+                variant.stem
+            ),
+            span,
+        ));
+    }
+
+    let mut paths = [None; VARIANTS.len()];
+    for (path, file) in paths.iter_mut().zip(&files) {
+        if let Some(file) = file {
+            *path = Some(
+                file.to_str()
+                    .ok_or_else(|| Error::new("icon path is not valid UTF-8", span))?,
+            );
+        }
+    }
+    Ok(row(&args.krate, &paths))
+}
+
+/// Cells of files that exist go through `__fa_embed`, which drops the styles disabled
+/// through Cargo features.
+fn row(krate: &TokenStream, files: &[Option<&str>]) -> TokenTree {
+    let mut row = TokenStream::new();
+    for (index, file) in files.iter().enumerate() {
+        match file {
+            Some(file) => {
+                let call = format!("::__fa_embed({index}, ::core::include_str!({file:?}))");
+                row.extend(krate.clone());
+                row.extend(call.parse::<TokenStream>().unwrap());
+            }
+            None => row.extend(
+                "::core::option::Option::None"
+                    .parse::<TokenStream>()
+                    .unwrap(),
+            ),
+        }
+        row.extend([punct(',')]);
+    }
+    TokenTree::Group(Group::new(Delimiter::Bracket, row))
+}
+
+fn parse_args(attr: TokenStream) -> Result<Args, Error> {
+    let mut base = None;
+    let mut krate = None;
+    let mut require = Vec::new();
+    let mut metadata = false;
+    let mut env_vars = Vec::new();
+    for arg in split_args(attr) {
+        let (key, value) = match arg.as_slice() {
+            [TokenTree::Ident(key), TokenTree::Punct(eq), value @ ..]
+                if eq.as_char() == '=' && !value.is_empty() =>
+            {
+                (key, value)
+            }
+            [first, ..] => {
+                return Err(Error::new(
+                    "expected `path = \"..\"`, `crate = path`, `require = [..]` or \
+                     `metadata = true`",
+                    first.span(),
+                ));
+            }
+            [] => continue,
+        };
+        match key.to_string().as_str() {
+            "path" => {
+                let (path, span) = path::eval(value, &mut env_vars)?;
+                base = Some((path::resolve(&path, span), span));
+            }
+            "crate" => krate = Some(value.iter().cloned().collect()),
+            "metadata" => {
+                metadata = match value {
+                    [TokenTree::Ident(value)] if value.to_string() == "true" => true,
+                    [TokenTree::Ident(value)] if value.to_string() == "false" => false,
+                    _ => return Err(Error::new("expected `true` or `false`", value[0].span())),
+                };
+            }
+            "require" => {
+                let [TokenTree::Group(group)] = value else {
+                    return Err(Error::new(
+                        "expected a list of style directories",
+                        value[0].span(),
+                    ));
+                };
+                for dir in split_args(group.stream()) {
+                    let (dir, span) = path::eval(&dir, &mut Vec::new())?;
+                    let Some(dir) = VARIANT_DIRS.iter().find(|d| **d == dir) else {
+                        return Err(Error::new(
+                            format!(
+                                "unknown style directory `{dir}`, expected one of: {}",
+                                VARIANT_DIRS.join(", ")
+                            ),
+                            span,
+                        ));
+                    };
+                    require.push(dir.as_str());
+                }
+            }
+            other => {
+                return Err(Error::new(
+                    format!("unknown argument `{other}`"),
+                    key.span(),
+                ));
+            }
+        }
+    }
+
+    let Some((base, base_span)) = base else {
+        return Err(Error::new(
+            "missing `path = \"..\"` argument",
+            Span::call_site(),
+        ));
+    };
+    if !base.is_dir() {
+        return Err(Error::new(
+            format!("icon directory `{}` does not exist", base.display()),
+            base_span,
+        ));
+    }
+    Ok(Args {
+        base,
+        krate: krate.unwrap_or_else(|| "::floem_fontawesome".parse().unwrap()),
+        require,
+        metadata,
+        env_vars,
+    })
+}
+
+fn parse_enum(item: TokenStream) -> Result<IconEnum, Error> {
+    let mut tokens = item
+        .into_iter()
+        .flat_map(flatten_none_group)
+        .collect::<Vec<_>>()
+        .into_iter()
+        .peekable();
+
+    let attrs = take_attrs(&mut tokens);
+    let mut vis = Vec::new();
+    let enum_token = loop {
+        match tokens.next() {
+            Some(TokenTree::Ident(ident)) if ident.to_string() == "enum" => break ident,
+            Some(TokenTree::Ident(ident)) if ident.to_string() == "pub" => {
+                vis.push(TokenTree::Ident(ident));
+                if let Some(TokenTree::Group(group)) = tokens.peek()
+                    && group.delimiter() == Delimiter::Parenthesis
+                {
+                    vis.extend(tokens.next());
+                }
+            }
+            token => {
+                return Err(Error::new(
+                    "`#[fa_icons]` can only be applied to an enum",
+                    token.map_or_else(Span::call_site, |token| token.span()),
+                ));
+            }
+        }
+    };
+    let Some(TokenTree::Ident(name)) = tokens.next() else {
+        return Err(Error::new("expected the enum name", enum_token.span()));
+    };
+    let body = match tokens.next() {
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => group,
+        token => {
+            return Err(Error::new(
+                "icon enums can't have generics or a where clause",
+                token.map_or_else(|| name.span(), |token| token.span()),
+            ));
+        }
+    };
+
+    let mut variants = Vec::new();
+    for variant in split_args(body.stream()) {
+        let mut tokens = variant.into_iter().peekable();
+        let attrs = take_attrs(&mut tokens);
+        let Some(TokenTree::Ident(ident)) = tokens.next() else {
+            return Err(Error::new("expected an icon variant", body.span()));
+        };
+        let stem = match tokens.next() {
+            None => file_stem(&ident.to_string()),
+            Some(TokenTree::Punct(eq)) if eq.as_char() == '=' => {
+                path::eval(&tokens.collect::<Vec<_>>(), &mut Vec::new())?.0
+            }
+            Some(token) => {
+                return Err(Error::new(
+                    "icon variants can't have fields, only a `= \"file-stem\"` override",
+                    token.span(),
+                ));
+            }
+        };
+        variants.push(Variant { attrs, ident, stem });
+    }
+
+    Ok(IconEnum {
+        attrs,
+        vis,
+        enum_token,
+        name,
+        brace_span: body.span(),
+        variants,
+    })
+}
+
+/// Takes the leading `#[..]` attributes, including doc comments.
+fn take_attrs(tokens: &mut std::iter::Peekable<impl Iterator<Item = TokenTree>>) -> Vec<TokenTree> {
+    let mut attrs = Vec::new();
+    while let Some(TokenTree::Punct(pound)) = tokens.peek()
+        && pound.as_char() == '#'
+    {
+        attrs.extend(tokens.next());
+        attrs.extend(tokens.next());
+    }
+    attrs
+}
+
+/// Parses `source` and replaces the placeholder identifiers in it.
+fn template(source: &str, substitutions: &[(&str, &TokenStream)]) -> TokenStream {
+    fn substitute(stream: TokenStream, substitutions: &[(&str, &TokenStream)]) -> TokenStream {
+        stream
+            .into_iter()
+            .flat_map(|token| match token {
+                TokenTree::Ident(ident) => {
+                    let name = ident.to_string();
+                    match substitutions.iter().find(|(from, _)| *from == name) {
+                        Some((_, to)) => (*to).clone(),
+                        None => TokenTree::Ident(ident).into(),
+                    }
+                }
+                TokenTree::Group(group) => {
+                    let stream = substitute(group.stream(), substitutions);
+                    TokenTree::Group(Group::new(group.delimiter(), stream)).into()
+                }
+                token => token.into(),
+            })
+            .collect()
+    }
+    substitute(source.parse().unwrap(), substitutions)
+}
+
+fn punct(c: char) -> TokenTree {
+    TokenTree::Punct(Punct::new(c, Spacing::Alone))
+}
@@ -0,0 +1,60 @@
+//! Procedural macros backing `floem-fontawesome`.
+//!
+//! These are re-exported from `floem-fontawesome`; depend on that crate instead of this one.
+
+mod icons;
+mod path;
+
+use proc_macro::TokenStream;
+
+/// Turns an enum into an icon set whose svgs are embedded from a Font Awesome `svgs/`
+/// directory.
+///
+/// ```ignore
+/// #[fa_icons(path = "../assets/fontawesome/svgs")]
+/// pub enum Icon {
+///     House,
+///     ArrowRight,
+///     Home = "house-chimney",
+/// }
+/// ```
+///
+/// Each variant is loaded from `<path>/<style>/<file stem>.svg`, where the file stem is the
+/// kebab-case form of the variant name unless given explicitly with `= "file-stem"`. Styles
+/// an icon doesn't exist in are skipped, but an icon with no svg at all is reported with an
+/// error on its variant.
+///
+/// Only the svgs that were found are tracked by Cargo. Adding a file the macro skipped, or
+/// removing a whole style directory, isn't noticed until the invoking source file changes;
+/// touch it to pick the change up, or generate the enum from a build script with
+/// `floem_fontawesome_codegen::Generator`, which reruns whenever the icon directory changes.
+///
+/// Arguments:
+///
+/// - `path`: the icon directory, as a string literal, `concat!` or `env!`. Relative paths
+///   are resolved against the invoking source file, like `include_str!` does. With
+///   `path = env!("FONTAWESOME_PRO_DIR")` the icons are rebuilt when the variable changes,
+///   and an unset variable is reported as a compile error. There's no fallback to the
+///   bundled Free icons here; a build script using
+///   `Generator::from_env("FONTAWESOME_PRO_DIR").free_fallback()` gets that, and also has
+///   Cargo watch the whole icon directory.
+/// - `require = ["solid", "regular"]`: style directories every icon must exist in. A
+///   missing file is reported with an error naming it, on the variant that lacks it.
+/// - `crate = path`: path of the `floem-fontawesome` crate, defaults to
+///   `::floem_fontawesome`.
+/// - `metadata = true`: reads `icon-families.json` or `icons.json` and `categories.yml`
+///   from the `metadata/` directory next to `path` for `FaIconTrait::info` and alias
+///   lookups. Off by default, since Font Awesome Pro's metadata runs to tens of megabytes
+///   and is parsed on every expansion; the build-script generator reads it once instead.
+///   The metadata isn't tracked either, so edits to it only show up after a rebuild.
+///
+/// The enum derives `Debug`, `Clone`, `Copy`, `PartialEq`, `Eq`, `PartialOrd`, `Ord` and
+/// `Hash`, allows `dead_code` so unused icons don't warn, and implements `FaIconTrait` and
+/// `IntoView`. `Display` writes the Font Awesome name and `FromStr` parses it back, also
+/// accepting an `fa-` prefix and, with `metadata = true`, the icon's aliases. `Icon::ALL`
+/// lists every icon in declaration order. Other attributes on the enum and its variants are
+/// kept.
+#[proc_macro_attribute]
+pub fn fa_icons(attr: TokenStream, item: TokenStream) -> TokenStream {
+    icons::expand(attr, item)
+}
@@ -0,0 +1,177 @@
+//! Compile-time evaluation of the icon paths handed to `#[fa_icons]`.
+
+use std::env;
+use std::path::{Path, PathBuf};
+
+use proc_macro::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+
+pub(crate) struct Error {
+    pub(crate) message: String,
+    pub(crate) span: Span,
+}
+
+impl Error {
+    pub(crate) fn new(message: impl Into<String>, span: Span) -> Self {
+        Self {
+            message: message.into(),
+            span,
+        }
+    }
+
+    /// A `compile_error!` invocation reporting this error at its span.
+    pub(crate) fn into_compile_error(self) -> TokenStream {
+        let span = self.span;
+        let mut message = Literal::string(&self.message);
+        message.set_span(span);
+        let mut args = Group::new(Delimiter::Parenthesis, TokenTree::Literal(message).into());
+        args.set_span(span);
+        let mut semi = Punct::new(';', Spacing::Alone);
+        semi.set_span(span);
+        [
+            TokenTree::Ident(Ident::new("compile_error", span)),
+            TokenTree::Punct(Punct::new('!', Spacing::Alone)),
+            TokenTree::Group(args),
+            TokenTree::Punct(semi),
+        ]
+        .into_iter()
+        .collect()
+    }
+}
+
+/// Unwraps the invisible groups macro_rules puts around captured fragments.
+pub(crate) fn flatten_none_group(token: TokenTree) -> Vec<TokenTree> {
+    match token {
+        TokenTree::Group(group) if group.delimiter() == Delimiter::None => group
+            .stream()
+            .into_iter()
+            .flat_map(flatten_none_group)
+            .collect(),
+        token => vec![token],
+    }
+}
+
+/// Splits a token stream on top-level commas, dropping a trailing empty argument.
+pub(crate) fn split_args(input: TokenStream) -> Vec<Vec<TokenTree>> {
+    let mut args = vec![Vec::new()];
+    for token in input.into_iter().flat_map(flatten_none_group) {
+        match token {
+            TokenTree::Punct(punct) if punct.as_char() == ',' => args.push(Vec::new()),
+            token => args.last_mut().unwrap().push(token),
+        }
+    }
+    if args.last().is_some_and(Vec::is_empty) {
+        args.pop();
+    }
+    args
+}
+
+/// Evaluates a string literal, `concat!(..)` or `env!(..)` expression.
+///
+/// Returns the string together with the span of its first literal, which relative paths
+/// are resolved against. The names of environment variables read are added to `env_vars`.
+pub(crate) fn eval(
+    tokens: &[TokenTree],
+    env_vars: &mut Vec<String>,
+) -> Result<(String, Span), Error> {
+    match tokens {
+        [TokenTree::Literal(literal)] => Ok((string_literal(literal)?, literal.span())),
+        [
+            TokenTree::Ident(name),
+            TokenTree::Punct(bang),
+            TokenTree::Group(group),
+        ] if bang.as_char() == '!' => {
+            let args = split_args(group.stream());
+            match name.to_string().as_str() {
+                "concat" => {
+                    let mut value = String::new();
+                    let mut span = None;
+                    for arg in &args {
+                        let (part, part_span) = eval(arg, env_vars)?;
+                        value.push_str(&part);
+                        span.get_or_insert(part_span);
+                    }
+                    Ok((value, span.unwrap_or_else(|| name.span())))
+                }
+                "env" => {
+                    let [var] = args.as_slice() else {
+                        return Err(Error::new("expected `env!(\"NAME\")`", group.span()));
+                    };
+                    let (var, span) = eval(var, env_vars)?;
+                    env_vars.push(var.clone());
+                    let value = env::var(&var).map_err(|_| {
+                        Error::new(format!("environment variable `{var}` not defined"), span)
+                    })?;
+                    Ok((value, span))
+                }
+                _ => Err(Error::new(
+                    "expected a string literal, `concat!` or `env!`",
+                    name.span(),
+                )),
+            }
+        }
+        [first, ..] => Err(Error::new(
+            "expected a string literal, `concat!` or `env!`",
+            first.span(),
+        )),
+        [] => Err(Error::new("expected a path", Span::call_site())),
+    }
+}
+
+/// Resolves `path` the way `include_str!` would when invoked at `span`.
+pub(crate) fn resolve(path: &str, span: Span) -> PathBuf {
+    let path = Path::new(path);
+    if path.is_absolute() {
+        return path.to_path_buf();
+    }
+    let dir = span
+        .local_file()
+        .and_then(|file| file.parent().map(Path::to_path_buf))
+        .or_else(|| env::var_os("CARGO_MANIFEST_DIR").map(PathBuf::from))
+        .unwrap_or_default();
+    let path = dir.join(path);
+    std::path::absolute(&path).unwrap_or(path)
+}
+
+fn string_literal(literal: &Literal) -> Result<String, Error> {
+    let text = literal.to_string();
+    let value = if let Some(raw) = text.strip_prefix('r') {
+        let hashes = raw.len() - raw.trim_start_matches('#').len();
+        raw.get(hashes..raw.len() - hashes)
+            .and_then(|raw| raw.strip_prefix('"'))
+            .and_then(|raw| raw.strip_suffix('"'))
+            .map(str::to_string)
+    } else {
+        text.strip_prefix('"')
+            .and_then(|text| text.strip_suffix('"'))
+            .and_then(unescape)
+    };
+    value.ok_or_else(|| Error::new("expected a string literal", literal.span()))
+}
+
+fn unescape(text: &str) -> Option<String> {
+    let mut value = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            value.push(c);
+            continue;
+        }
+        value.push(match chars.next()? {
+            'n' => '\n',
+            'r' => '\r',
+            't' => '\t',
+            '0' => '\0',
+            '\\' => '\\',
+            '"' => '"',
+            '\'' => '\'',
+            'u' => {
+                let rest = chars.as_str().strip_prefix('{')?;
+                let (hex, rest) = rest.split_once('}')?;
+                chars = rest.chars();
+                char::from_u32(u32::from_str_radix(hex, 16).ok()?)?
+            }
+            _ => return None,
+        });
+    }
+    Some(value)
+}
@@ -0,0 +1,29 @@
+#!/bin/sh
+# Vendors the Font Awesome Free package used by the `free` feature into
+# assets/fontawesome-free. The result is committed, so building with `free` never
+# downloads anything; rerun this only to move to another release, given as the argument.
+set -eu
+
+VERSION="${1:-6.7.2}"
+ROOT="$(cd "$(dirname "$0")/.." && pwd)"
+DEST="$ROOT/assets/fontawesome-free"
+TMP="$(mktemp -d)"
+trap 'rm -rf "$TMP"' EXIT
+
+curl -fsSL -o "$TMP/fontawesome.zip" \
+    "https://use.fontawesome.com/releases/v$VERSION/fontawesome-free-$VERSION-web.zip"
+unzip -q "$TMP/fontawesome.zip" -d "$TMP"
+PACKAGE="$TMP/fontawesome-free-$VERSION-web"
+
+rm -rf "$DEST/svgs" "$DEST/metadata"
+mkdir -p "$DEST/svgs" "$DEST/metadata"
+for style in solid regular brands; do
+    cp -R "$PACKAGE/svgs/$style" "$DEST/svgs/$style"
+done
+for file in icon-families.json icons.json categories.yml; do
+    if [ -f "$PACKAGE/metadata/$file" ]; then
+        cp "$PACKAGE/metadata/$file" "$DEST/metadata/$file"
+    fi
+done
+cp "$PACKAGE/LICENSE.txt" "$DEST/LICENSE.txt"
+echo "Font Awesome Free $VERSION vendored in $DEST, commit it"
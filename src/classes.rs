@@ -0,0 +1,341 @@
+use std::str::FromStr;
+
+use crate::{
+    FaBaseStyle, FaFamily, FaSize, FaStyle, FaUnknownIconError, FaVariant, FaVariantError,
+};
+
+/// An icon parsed from a Font Awesome class list, as found in HTML snippets like
+/// `<i class="fa-sharp fa-solid fa-user fa-spin fa-2x">`.
+///
+/// Family and style classes (`fa-sharp`, `fa-duotone`, `fa-solid`, ...) and their legacy
+/// shorthands (`fas`, `far`, `fab`, `fad`, `fass`, ...) make up the variant, the icon class
+/// is looked up with the icon enum's `FromStr` and everything else must be a known
+/// [`FaModifier`].
+///
+/// ```ignore
+/// let classes: FaClasses<Icon> = "fa-sharp fa-duotone fa-solid fa-user fa-fw".parse()?;
+/// fa_icon(classes.icon).custom_style(move |_| classes.style())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FaClasses<T> {
+    pub icon: T,
+    pub variant: FaVariant,
+    /// Modifier classes in the order they appeared.
+    pub modifiers: Vec<FaModifier>,
+}
+
+/// A Font Awesome utility class that isn't part of the icon or its variant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FaModifier {
+    /// `fa-2xs`, `fa-xs`, `fa-sm`, `fa-lg`, `fa-xl`, `fa-2xl` and `fa-1x` to `fa-10x`.
+    Size(FaSize),
+    /// `fa-fw`
+    FixedWidth,
+    /// `fa-swap-opacity`
+    SwapOpacity,
+    /// `fa-rotate-90`, `fa-rotate-180` and `fa-rotate-270`, in degrees.
+    Rotate(u16),
+    /// `fa-rotate-by`, which rotates by the `--fa-rotate-angle` CSS variable.
+    RotateBy,
+    /// `fa-flip-horizontal`
+    FlipHorizontal,
+    /// `fa-flip-vertical`
+    FlipVertical,
+    /// `fa-flip-both`
+    FlipBoth,
+    /// `fa-beat`, `fa-bounce`, `fa-fade`, `fa-beat-fade`, `fa-flip`, `fa-shake`, `fa-spin`,
+    /// `fa-spin-pulse`, `fa-spin-reverse` and `fa-pulse`, holding the class without its
+    /// `fa-` prefix.
+    Animation(&'static str),
+    /// `fa-border`
+    Border,
+    /// `fa-pull-left`
+    PullLeft,
+    /// `fa-pull-right`
+    PullRight,
+    /// `fa-inverse`
+    Inverse,
+    /// `fa-ul`, on a list whose items have `fa-li` icons as bullets.
+    List,
+    /// `fa-li`
+    ListItem,
+    /// `fa-stack`, on the container of stacked icons.
+    Stack,
+    /// `fa-stack-1x`, a stacked icon at the stack's line height.
+    Stack1x,
+    /// `fa-stack-2x`, a stacked icon filling the stack.
+    Stack2x,
+}
+
+/// Why a class list couldn't be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FaClassError {
+    /// A class that is neither a family, style, icon nor modifier.
+    UnknownClass(String),
+    /// No class named an icon.
+    MissingIcon,
+    /// More than one class named an icon.
+    MultipleIcons(String),
+    /// The family and style classes name a combination Font Awesome doesn't ship.
+    InvalidVariant(FaVariantError),
+}
+
+impl std::fmt::Display for FaClassError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FaClassError::UnknownClass(class) => write!(f, "unknown Font Awesome class `{class}`"),
+            FaClassError::MissingIcon => write!(f, "no Font Awesome icon class"),
+            FaClassError::MultipleIcons(class) => {
+                write!(
+                    f,
+                    "more than one Font Awesome icon class, `{class}` is extra"
+                )
+            }
+            FaClassError::InvalidVariant(error) => error.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for FaClassError {}
+
+const ANIMATIONS: [&str; 10] = [
+    "beat",
+    "bounce",
+    "fade",
+    "beat-fade",
+    "flip",
+    "shake",
+    "spin",
+    "spin-pulse",
+    "spin-reverse",
+    "pulse",
+];
+
+impl<T: FromStr<Err = FaUnknownIconError>> FromStr for FaClasses<T> {
+    type Err = FaClassError;
+
+    fn from_str(classes: &str) -> Result<Self, Self::Err> {
+        let mut icon = None;
+        let mut variant = FaVariant::default();
+        let mut modifiers = Vec::new();
+
+        for class in classes.split_whitespace() {
+            if apply_variant_class(&mut variant, class) {
+                continue;
+            }
+            if let Some(modifier) = modifier(class) {
+                modifiers.push(modifier);
+                continue;
+            }
+            if !class.starts_with("fa-") {
+                return Err(FaClassError::UnknownClass(class.to_string()));
+            }
+            match class.parse() {
+                Ok(_) if icon.is_some() => {
+                    return Err(FaClassError::MultipleIcons(class.to_string()));
+                }
+                Ok(parsed) => icon = Some(parsed),
+                Err(FaUnknownIconError { .. }) => {
+                    return Err(FaClassError::UnknownClass(class.to_string()));
+                }
+            }
+        }
+
+        variant.validate().map_err(FaClassError::InvalidVariant)?;
+        Ok(Self {
+            icon: icon.ok_or(FaClassError::MissingIcon)?,
+            variant,
+            modifiers,
+        })
+    }
+}
+
+impl<T> FaClasses<T> {
+    /// The style for these classes.
+    ///
+    /// The variant, the last size class, `fa-fw` and `fa-swap-opacity` are applied; other
+    /// modifiers are left for the caller to interpret.
+    pub fn style(&self) -> FaStyle {
+        let mut style = FaStyle::default().variant(self.variant);
+        for modifier in &self.modifiers {
+            style = match modifier {
+                FaModifier::Size(size) => style.size(*size),
+                FaModifier::FixedWidth => style.fixed_width(),
+                FaModifier::SwapOpacity => style.swap_opacity(),
+                _ => style,
+            };
+        }
+        style
+    }
+}
+
+/// Applies a family or style class to `variant`, returning whether `class` was one.
+///
+/// `fa-sharp` and `fa-duotone` combine with each other into `sharp-duotone`; any other
+/// family class replaces the family.
+pub(crate) fn apply_variant_class(variant: &mut FaVariant, class: &str) -> bool {
+    use FaBaseStyle::*;
+    use FaFamily::*;
+
+    let (family, base) = match class {
+        // `fa` on its own is the Font Awesome 4 and 5 base class.
+        "fa" => (Some(Classic), None),
+        "fa-sharp" => (Some(variant.family.with_sharp(true)), None),
+        "fa-duotone" => (Some(variant.family.with_duotone(true)), None),
+
+        // Legacy shorthands
+        "fas" => (Some(Classic), Some(Solid)),
+        "far" => (Some(Classic), Some(Regular)),
+        "fal" => (Some(Classic), Some(Light)),
+        "fat" => (Some(Classic), Some(Thin)),
+        "fab" => (Some(Classic), Some(Brands)),
+        "fad" => (Some(Duotone), Some(Solid)),
+        "fass" => (Some(Sharp), Some(Solid)),
+        "fasr" => (Some(Sharp), Some(Regular)),
+        "fasl" => (Some(Sharp), Some(Light)),
+        "fast" => (Some(Sharp), Some(Thin)),
+        "fasds" => (Some(SharpDuotone), Some(Solid)),
+        _ => {
+            let Some(name) = class.strip_prefix("fa-") else {
+                return false;
+            };
+            if let Ok(family) = name.parse() {
+                (Some(family), None)
+            } else if let Ok(base) = name.parse() {
+                (None, Some(base))
+            } else {
+                return false;
+            }
+        }
+    };
+    if let Some(family) = family {
+        variant.family = family;
+    }
+    if let Some(base) = base {
+        variant.base = base;
+    }
+    true
+}
+
+fn modifier(class: &str) -> Option<FaModifier> {
+    let name = class.strip_prefix("fa-")?;
+    let modifier = match name {
+        "fw" => FaModifier::FixedWidth,
+        "swap-opacity" => FaModifier::SwapOpacity,
+        "rotate-90" => FaModifier::Rotate(90),
+        "rotate-180" => FaModifier::Rotate(180),
+        "rotate-270" => FaModifier::Rotate(270),
+        "rotate-by" => FaModifier::RotateBy,
+        "flip-horizontal" => FaModifier::FlipHorizontal,
+        "flip-vertical" => FaModifier::FlipVertical,
+        "flip-both" => FaModifier::FlipBoth,
+        "border" => FaModifier::Border,
+        "pull-left" => FaModifier::PullLeft,
+        "pull-right" => FaModifier::PullRight,
+        "inverse" => FaModifier::Inverse,
+        "ul" => FaModifier::List,
+        "li" => FaModifier::ListItem,
+        "stack" => FaModifier::Stack,
+        "stack-1x" => FaModifier::Stack1x,
+        "stack-2x" => FaModifier::Stack2x,
+        _ => {
+            if let Ok(size) = name.parse() {
+                FaModifier::Size(size)
+            } else {
+                FaModifier::Animation(ANIMATIONS.into_iter().find(|anim| *anim == name)?)
+            }
+        }
+    };
+    Some(modifier)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fa_icons;
+    use floem::style::Style;
+
+    #[fa_icons(path = "../tests/fixtures/svgs", metadata = true, crate = crate)]
+    enum Icon {
+        House,
+        Github,
+    }
+
+    #[test]
+    fn test_parse_classes() {
+        let classes: FaClasses<Icon> = "fa-sharp fa-duotone fa-solid fa-house fa-spin fa-2x fa-fw"
+            .parse()
+            .unwrap();
+        assert_eq!(classes.icon, Icon::House);
+        assert_eq!(
+            classes.variant,
+            FaVariant::new(FaFamily::SharpDuotone, FaBaseStyle::Solid)
+        );
+        assert_eq!(
+            classes.modifiers,
+            [
+                FaModifier::Animation("spin"),
+                FaModifier::Size(FaSize::Times(2)),
+                FaModifier::FixedWidth
+            ]
+        );
+        assert_eq!(
+            Style::from(classes.style()).get(crate::FaSizeProp),
+            Some(FaSize::Times(2))
+        );
+        assert!(Style::from(classes.style()).get(crate::FaFixedWidth));
+
+        let classes: FaClasses<Icon> = "fa fa-home far".parse().unwrap();
+        assert_eq!(classes.icon, Icon::House);
+        assert_eq!(
+            classes.variant,
+            FaVariant::new(FaFamily::Classic, FaBaseStyle::Regular)
+        );
+        let classes: FaClasses<Icon> = "fab fa-github".parse().unwrap();
+        assert_eq!(classes.variant, FaVariant::BRANDS);
+        let classes: FaClasses<Icon> = "fad fa-house fa-rotate-90".parse().unwrap();
+        assert_eq!(
+            classes.variant,
+            FaVariant::new(FaFamily::Duotone, FaBaseStyle::Solid)
+        );
+        assert_eq!(classes.modifiers, [FaModifier::Rotate(90)]);
+
+        // Snippets from Font Awesome's list and stacking docs
+        let classes: FaClasses<Icon> = "fa-li fa-solid fa-house fa-rotate-by".parse().unwrap();
+        assert_eq!(
+            classes.modifiers,
+            [FaModifier::ListItem, FaModifier::RotateBy]
+        );
+        let classes: FaClasses<Icon> = "fa-solid fa-house fa-stack-2x fa-inverse".parse().unwrap();
+        assert_eq!(
+            classes.modifiers,
+            [FaModifier::Stack2x, FaModifier::Inverse]
+        );
+        assert_eq!(modifier("fa-stack"), Some(FaModifier::Stack));
+        assert_eq!(modifier("fa-stack-1x"), Some(FaModifier::Stack1x));
+        assert_eq!(modifier("fa-ul"), Some(FaModifier::List));
+    }
+
+    #[test]
+    fn test_parse_class_errors() {
+        let parse = |classes: &str| classes.parse::<FaClasses<Icon>>().unwrap_err();
+        assert_eq!(
+            parse("fa-solid fa-user"),
+            FaClassError::UnknownClass("fa-user".into())
+        );
+        assert_eq!(
+            parse("fa-house text-lg"),
+            FaClassError::UnknownClass("text-lg".into())
+        );
+        assert_eq!(parse("fa-solid fa-spin"), FaClassError::MissingIcon);
+        assert_eq!(
+            parse("fa-house fa-github"),
+            FaClassError::MultipleIcons("fa-github".into())
+        );
+        assert!(matches!(
+            parse("fa-sharp fa-brands fa-github"),
+            FaClassError::InvalidVariant(_)
+        ));
+    }
+}
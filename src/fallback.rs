@@ -0,0 +1,147 @@
+use std::sync::{PoisonError, RwLock};
+
+use floem::style::StylePropValue;
+
+use crate::{FaBaseStyle, FaFamily, FaVariant};
+
+static GLOBAL_FALLBACK: RwLock<FaFallback> = RwLock::new(FaFallback::Auto);
+
+/// Weights from lightest to heaviest.
+const WEIGHTS: [FaBaseStyle; 5] = [
+    FaBaseStyle::Thin,
+    FaBaseStyle::Light,
+    FaBaseStyle::Regular,
+    FaBaseStyle::Semibold,
+    FaBaseStyle::Solid,
+];
+
+/// What an icon renders when it wasn't embedded in the requested variant.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum FaFallback {
+    /// Use the process-wide policy set with [`FaFallback::set_global`].
+    #[default]
+    Global,
+    /// Render an empty svg.
+    Disabled,
+    /// Drop the sharp and duotone modifiers (`jelly-duo` → `jelly`), then move towards
+    /// heavier classic weights (sharp-thin → thin → light → regular → solid), then lighter
+    /// ones, and finally any variant the icon has at all, such as `brands`.
+    Auto,
+    /// Try these variants in order after the requested one.
+    Chain(Vec<FaVariant>),
+}
+
+impl StylePropValue for FaFallback {}
+
+impl FaFallback {
+    /// Sets the policy used by icons whose fallback is [`FaFallback::Global`].
+    /// Defaults to [`FaFallback::Auto`].
+    pub fn set_global(fallback: FaFallback) {
+        *GLOBAL_FALLBACK
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = fallback;
+    }
+
+    /// The process-wide policy.
+    pub fn global() -> FaFallback {
+        GLOBAL_FALLBACK
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Variants to try for `requested`, in order, starting with `requested` itself.
+    pub fn candidates(&self, requested: FaVariant) -> Vec<FaVariant> {
+        let mut candidates = vec![requested];
+        match self {
+            FaFallback::Global => match FaFallback::global() {
+                // A global policy of `Global` would never resolve.
+                FaFallback::Global => return FaFallback::Auto.candidates(requested),
+                global => return global.candidates(requested),
+            },
+            FaFallback::Disabled => {}
+            FaFallback::Auto => {
+                candidates.extend([
+                    requested.with_family(requested.family.with_sharp(false)),
+                    requested.with_family(requested.family.with_duotone(false)),
+                ]);
+                let weight = WEIGHTS
+                    .iter()
+                    .position(|base| *base == requested.base)
+                    .unwrap_or(WEIGHTS.len() - 1);
+                let heavier = WEIGHTS[weight..].iter();
+                let lighter = WEIGHTS[..weight].iter().rev();
+                candidates.extend(
+                    heavier
+                        .chain(lighter)
+                        .map(|base| FaVariant::new(FaFamily::Classic, *base)),
+                );
+                candidates.extend(FaVariant::ALL.iter().copied());
+                // Skip combinations Font Awesome doesn't ship, such as classic semibold.
+                candidates
+                    .retain(|candidate| *candidate == requested || candidate.index().is_some());
+            }
+            FaFallback::Chain(chain) => candidates.extend(chain),
+        }
+
+        let mut unique = Vec::with_capacity(candidates.len());
+        for candidate in candidates {
+            if !unique.contains(&candidate) {
+                unique.push(candidate);
+            }
+        }
+        unique
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_auto_candidates() {
+        let sharp_thin = FaVariant::new(FaFamily::Sharp, FaBaseStyle::Thin);
+        let candidates = FaFallback::Auto.candidates(sharp_thin);
+        assert_eq!(
+            candidates[..5],
+            [
+                sharp_thin,
+                FaVariant::new(FaFamily::Classic, FaBaseStyle::Thin),
+                FaVariant::new(FaFamily::Classic, FaBaseStyle::Light),
+                FaVariant::new(FaFamily::Classic, FaBaseStyle::Regular),
+                FaVariant::new(FaFamily::Classic, FaBaseStyle::Solid),
+            ]
+        );
+        assert_eq!(candidates.len(), FaVariant::ALL.len());
+    }
+
+    #[test]
+    fn test_family_candidates() {
+        let jelly_duo = FaVariant::new(FaFamily::JellyDuo, FaBaseStyle::Regular);
+        let candidates = FaFallback::Auto.candidates(jelly_duo);
+        assert_eq!(
+            candidates[..3],
+            [
+                jelly_duo,
+                FaVariant::new(FaFamily::Jelly, FaBaseStyle::Regular),
+                FaVariant::new(FaFamily::Classic, FaBaseStyle::Regular),
+            ]
+        );
+        assert!(
+            candidates
+                .iter()
+                .all(|candidate| candidate.validate().is_ok())
+        );
+    }
+
+    #[test]
+    fn test_chain_candidates() {
+        let solid = FaVariant::default();
+        let brands = FaVariant::new(FaFamily::Classic, FaBaseStyle::Brands);
+        assert_eq!(
+            FaFallback::Chain(vec![solid, brands]).candidates(solid),
+            [solid, brands]
+        );
+        assert_eq!(FaFallback::Disabled.candidates(brands), [brands]);
+    }
+}
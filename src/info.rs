@@ -0,0 +1,38 @@
+use crate::FaVariant;
+
+/// What Font Awesome's metadata says about an icon.
+///
+/// Generated from the `metadata/` directory next to the `svgs/` directory icons are
+/// embedded from: `icon-families.json` (or `icons.json` for older releases) and
+/// `categories.yml`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FaIconInfo {
+    /// Font Awesome name, e.g. `arrow-right`.
+    pub name: &'static str,
+    /// Human readable name, e.g. `Arrow right`.
+    pub label: &'static str,
+    /// Code point of the icon in the Font Awesome fonts.
+    pub unicode: Option<char>,
+    pub search_terms: &'static [&'static str],
+    /// Former names of the icon, e.g. `home` for `house`.
+    pub aliases: &'static [&'static str],
+    /// Labels of the categories the icon is listed in.
+    pub categories: &'static [&'static str],
+    /// Variants Font Awesome ships the icon in, whether or not they were embedded.
+    pub styles: &'static [FaVariant],
+    /// Font Awesome version that introduced the icon.
+    pub version_added: Option<&'static str>,
+}
+
+impl FaIconInfo {
+    /// Whether `query` matches the icon's name, label, aliases or search terms, ignoring
+    /// case.
+    pub fn matches(&self, query: &str) -> bool {
+        let query = query.trim().to_lowercase();
+        [self.name, self.label]
+            .into_iter()
+            .chain(self.aliases.iter().copied())
+            .chain(self.search_terms.iter().copied())
+            .any(|text| text.to_lowercase().contains(&query))
+    }
+}
@@ -1,70 +1,558 @@
-use floem::style::TextColor;
+use floem::style::{FontSize, TextColor};
 use floem::views::{SvgCssPropExtractor, SvgOrStyle, brush_to_css_string};
 use floem::{
     View, ViewId,
-    peniko::Brush,
+    peniko::{Brush, Color},
     prop, prop_extractor,
+    reactive::{RwSignal, SignalGet, SignalUpdate, create_updater},
     style::{CustomStyle, Style, StylePropValue, Transition},
     style_class,
     views::Decorators,
 };
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+mod classes;
+mod fallback;
+mod info;
+mod serde_impls;
+mod size;
+
+pub use classes::{FaClassError, FaClasses, FaModifier};
+pub use fallback::FaFallback;
+pub use info::FaIconInfo;
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+pub use serde as __serde;
+#[cfg(feature = "serde")]
+pub use serde_impls::__fa_deserialize_str;
+pub use size::{FaSize, FaUnknownSizeError};
+
+/// The Font Awesome Free icons bundled with this crate, enabled by the `free` feature.
+///
+/// [`free::Icon`] has every free icon in the solid, regular and brands styles, so
+/// `fa_icon(free::Icon::House)` works without vendoring any svgs. The icons are licensed
+/// under CC BY 4.0 by Fonticons, Inc.
+#[cfg(feature = "free")]
+pub mod free {
+    include!(concat!(env!("OUT_DIR"), "/fa_free.rs"));
+}
+
+/// A family and style of Font Awesome icons, each with its own directory in a Font Awesome
+/// `svgs/` tree, such as `sharp-light` or `jelly-duo-regular`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub struct FaVariant {
-    // Base style
+    pub family: FaFamily,
     pub base: FaBaseStyle,
-    // Modifiers
-    pub is_sharp: bool,
-    pub is_duotone: bool,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// The style, or weight, of a [`FaVariant`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
 pub enum FaBaseStyle {
+    #[default]
     Solid,
     Regular,
     Light,
     Thin,
+    Semibold,
     Brands,
 }
 
-impl Default for FaVariant {
-    fn default() -> Self {
-        Self {
-            base: FaBaseStyle::Solid,
-            is_sharp: false,
-            is_duotone: false,
+/// The family of a [`FaVariant`].
+///
+/// Families only ship some of the styles; [`FaVariant::ALL`] lists the combinations that
+/// exist.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum FaFamily {
+    #[default]
+    Classic,
+    Sharp,
+    Duotone,
+    SharpDuotone,
+    Chisel,
+    Etch,
+    Jelly,
+    JellyDuo,
+    JellyFill,
+    Notdog,
+    NotdogDuo,
+    Slab,
+    SlabPress,
+    Thumbprint,
+    Whiteboard,
+    Utility,
+    UtilityDuo,
+    UtilityFill,
+}
+
+impl FaFamily {
+    /// Every family, in the order their variants first appear in [`FaVariant::ALL`].
+    pub const ALL: &'static [FaFamily] = &families::<{ family_count() }>();
+
+    /// Font Awesome's name for the family, as used in directory names and after `fa-` in
+    /// CSS classes.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            FaFamily::Classic => "classic",
+            FaFamily::Sharp => "sharp",
+            FaFamily::Duotone => "duotone",
+            FaFamily::SharpDuotone => "sharp-duotone",
+            FaFamily::Chisel => "chisel",
+            FaFamily::Etch => "etch",
+            FaFamily::Jelly => "jelly",
+            FaFamily::JellyDuo => "jelly-duo",
+            FaFamily::JellyFill => "jelly-fill",
+            FaFamily::Notdog => "notdog",
+            FaFamily::NotdogDuo => "notdog-duo",
+            FaFamily::Slab => "slab",
+            FaFamily::SlabPress => "slab-press",
+            FaFamily::Thumbprint => "thumbprint",
+            FaFamily::Whiteboard => "whiteboard",
+            FaFamily::Utility => "utility",
+            FaFamily::UtilityDuo => "utility-duo",
+            FaFamily::UtilityFill => "utility-fill",
+        }
+    }
+
+    pub const fn is_sharp(&self) -> bool {
+        matches!(self, FaFamily::Sharp | FaFamily::SharpDuotone)
+    }
+
+    /// Whether the family's svgs have separately styled `fa-primary` and `fa-secondary`
+    /// layers.
+    pub const fn is_duotone(&self) -> bool {
+        matches!(
+            self,
+            FaFamily::Duotone
+                | FaFamily::SharpDuotone
+                | FaFamily::JellyDuo
+                | FaFamily::NotdogDuo
+                | FaFamily::UtilityDuo
+        )
+    }
+
+    /// The sharp or non-sharp counterpart of this family. Families without a sharp version
+    /// become [`FaFamily::Sharp`] when asked for one.
+    pub const fn with_sharp(self, sharp: bool) -> Self {
+        match (self, sharp) {
+            (FaFamily::Sharp | FaFamily::SharpDuotone, true) => self,
+            (FaFamily::Duotone, true) => FaFamily::SharpDuotone,
+            (_, true) => FaFamily::Sharp,
+            (FaFamily::Sharp, false) => FaFamily::Classic,
+            (FaFamily::SharpDuotone, false) => FaFamily::Duotone,
+            (_, false) => self,
+        }
+    }
+
+    /// The duotone or single color counterpart of this family, e.g. `jelly-duo` for
+    /// `jelly`. Families without a two-tone version become [`FaFamily::Duotone`] when asked
+    /// for one.
+    pub const fn with_duotone(self, duotone: bool) -> Self {
+        if self.is_duotone() == duotone {
+            return self;
+        }
+        match (self, duotone) {
+            (FaFamily::Sharp, true) => FaFamily::SharpDuotone,
+            (FaFamily::Jelly | FaFamily::JellyFill, true) => FaFamily::JellyDuo,
+            (FaFamily::Notdog, true) => FaFamily::NotdogDuo,
+            (FaFamily::Utility | FaFamily::UtilityFill, true) => FaFamily::UtilityDuo,
+            (_, true) => FaFamily::Duotone,
+            (FaFamily::SharpDuotone, false) => FaFamily::Sharp,
+            (FaFamily::JellyDuo, false) => FaFamily::Jelly,
+            (FaFamily::NotdogDuo, false) => FaFamily::Notdog,
+            (FaFamily::UtilityDuo, false) => FaFamily::Utility,
+            (_, false) => FaFamily::Classic,
+        }
+    }
+}
+
+/// Whether a variant before `index` in [`FaVariant::ALL`] is of the same family.
+const fn family_seen(index: usize) -> bool {
+    let family = FaVariant::ALL[index].family as u8;
+    let mut earlier = 0;
+    while earlier < index {
+        if FaVariant::ALL[earlier].family as u8 == family {
+            return true;
         }
+        earlier += 1;
     }
+    false
 }
+
+const fn family_count() -> usize {
+    let mut count = 0;
+    let mut index = 0;
+    while index < FaVariant::ALL.len() {
+        if !family_seen(index) {
+            count += 1;
+        }
+        index += 1;
+    }
+    count
+}
+
+const fn families<const N: usize>() -> [FaFamily; N] {
+    let mut families = [FaFamily::Classic; N];
+    let mut count = 0;
+    let mut index = 0;
+    while index < FaVariant::ALL.len() {
+        if !family_seen(index) {
+            families[count] = FaVariant::ALL[index].family;
+            count += 1;
+        }
+        index += 1;
+    }
+    families
+}
+
+impl std::fmt::Display for FaFamily {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl std::str::FromStr for FaFamily {
+    type Err = FaUnknownVariantError;
+
+    /// Parses a family name such as `sharp-duotone`, optionally as a CSS class
+    /// (`fa-sharp-duotone`).
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        let trimmed = name.trim();
+        let family = trimmed.strip_prefix("fa-").unwrap_or(trimmed);
+        FaFamily::ALL
+            .iter()
+            .copied()
+            .find(|candidate| candidate.name() == family)
+            .ok_or_else(|| FaUnknownVariantError {
+                name: name.to_string(),
+            })
+    }
+}
+
 impl std::fmt::Display for FaVariant {
+    /// Writes the variant's directory name, e.g. `duotone` or `sharp-thin`: the bare style
+    /// for the classic family, `duotone` for solid duotone and `<family>-<style>` otherwise.
+    /// Combinations without a directory, such as sharp brands, follow the same rule.
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut parts = Vec::new();
-        if self.is_sharp {
-            parts.push("sharp");
+        match (self.family, self.base) {
+            (FaFamily::Classic, base) => base.fmt(f),
+            (FaFamily::Duotone, FaBaseStyle::Solid) => f.write_str("duotone"),
+            (family, base) => write!(f, "{family}-{base}"),
         }
-        if self.is_duotone {
-            parts.push("duotone");
-        }
-        parts.push(match self.base {
+    }
+}
+
+impl std::str::FromStr for FaVariant {
+    type Err = FaUnknownVariantError;
+
+    /// Parses a directory name (`duotone`, `sharp-light`), a family/style pair
+    /// (`sharp-duotone/thin`, `classic/regular`), a bare family (`jelly`), a bare style
+    /// (`thin`) or a list of CSS classes (`fa-sharp fa-solid`, `fasr`).
+    ///
+    /// A bare family stands for its first variant in [`FaVariant::ALL`], so `duotone` is
+    /// duotone solid and `jelly` is jelly regular.
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        let unknown = || FaUnknownVariantError {
+            name: name.to_string(),
+        };
+        let trimmed = name.trim();
+        let variant = if trimmed.starts_with("fa") {
+            let mut variant = FaVariant::default();
+            for class in trimmed.split_whitespace() {
+                if !classes::apply_variant_class(&mut variant, class) {
+                    return Err(unknown());
+                }
+            }
+            variant
+        } else if let Some((family, style)) = trimmed.split_once('/') {
+            let family = family.parse().map_err(|_| unknown())?;
+            Self::new(family, style.parse().map_err(|_| unknown())?)
+        } else {
+            // `<family>-<style>`, a bare family or a bare style. The longest matching
+            // family wins, so `sharp-duotone-thin` isn't read as the sharp family.
+            let family = FaFamily::ALL
+                .iter()
+                .filter(|family| {
+                    trimmed
+                        .strip_prefix(family.name())
+                        .is_some_and(|rest| rest.is_empty() || rest.starts_with('-'))
+                })
+                .max_by_key(|family| family.name().len());
+            match family {
+                Some(&family) => match trimmed[family.name().len()..].trim_start_matches('-') {
+                    "" => Self::ALL
+                        .iter()
+                        .copied()
+                        .find(|variant| variant.family == family)
+                        .ok_or_else(unknown)?,
+                    style => Self::new(family, style.parse().map_err(|_| unknown())?),
+                },
+                None => Self::new(FaFamily::Classic, trimmed.parse().map_err(|_| unknown())?),
+            }
+        };
+        variant.validate().map_err(|_| unknown())?;
+        Ok(variant)
+    }
+}
+
+impl std::fmt::Display for FaBaseStyle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
             FaBaseStyle::Solid => "solid",
             FaBaseStyle::Regular => "regular",
             FaBaseStyle::Light => "light",
             FaBaseStyle::Thin => "thin",
+            FaBaseStyle::Semibold => "semibold",
             FaBaseStyle::Brands => "brands",
-        });
-        write!(f, "{}", parts.join("-"))
+        })
     }
 }
 
+impl std::str::FromStr for FaBaseStyle {
+    type Err = FaUnknownVariantError;
+
+    /// Parses a style name such as `solid`, optionally as a CSS class (`fa-solid`).
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        let trimmed = name.trim();
+        match trimmed.strip_prefix("fa-").unwrap_or(trimmed) {
+            "solid" => Ok(FaBaseStyle::Solid),
+            "regular" => Ok(FaBaseStyle::Regular),
+            "light" => Ok(FaBaseStyle::Light),
+            "thin" => Ok(FaBaseStyle::Thin),
+            "semibold" => Ok(FaBaseStyle::Semibold),
+            "brands" => Ok(FaBaseStyle::Brands),
+            _ => Err(FaUnknownVariantError {
+                name: name.to_string(),
+            }),
+        }
+    }
+}
+
+/// Position of each family and style in [`FaVariant::ALL`], by discriminant, so that svg
+/// lookups don't search the list.
+const VARIANT_INDEX: [[Option<usize>; FaBaseStyle::Brands as usize + 1]; FaFamily::ALL.len()] = {
+    let mut table = [[None; FaBaseStyle::Brands as usize + 1]; FaFamily::ALL.len()];
+    let mut index = 0;
+    while index < FaVariant::ALL.len() {
+        let variant = FaVariant::ALL[index];
+        table[variant.family as usize][variant.base as usize] = Some(index);
+        index += 1;
+    }
+    table
+};
+
 impl FaVariant {
-    pub fn is_duotone(&self) -> bool {
-        self.is_duotone
+    /// Every variant with its own directory in a Font Awesome `svgs/` tree, in the column
+    /// order of the embedded svg tables. New families are appended, so indices stay put.
+    pub const ALL: &'static [FaVariant] = &[
+        Self::new(FaFamily::Classic, FaBaseStyle::Solid),
+        Self::new(FaFamily::Classic, FaBaseStyle::Regular),
+        Self::new(FaFamily::Classic, FaBaseStyle::Light),
+        Self::new(FaFamily::Classic, FaBaseStyle::Thin),
+        Self::new(FaFamily::Duotone, FaBaseStyle::Solid),
+        Self::new(FaFamily::Sharp, FaBaseStyle::Solid),
+        Self::new(FaFamily::Sharp, FaBaseStyle::Regular),
+        Self::new(FaFamily::Sharp, FaBaseStyle::Light),
+        Self::new(FaFamily::Sharp, FaBaseStyle::Thin),
+        Self::new(FaFamily::SharpDuotone, FaBaseStyle::Solid),
+        Self::new(FaFamily::SharpDuotone, FaBaseStyle::Regular),
+        Self::new(FaFamily::SharpDuotone, FaBaseStyle::Light),
+        Self::new(FaFamily::SharpDuotone, FaBaseStyle::Thin),
+        Self::new(FaFamily::Duotone, FaBaseStyle::Regular),
+        Self::new(FaFamily::Duotone, FaBaseStyle::Light),
+        Self::new(FaFamily::Duotone, FaBaseStyle::Thin),
+        Self::new(FaFamily::Classic, FaBaseStyle::Brands),
+        Self::new(FaFamily::Chisel, FaBaseStyle::Regular),
+        Self::new(FaFamily::Etch, FaBaseStyle::Solid),
+        Self::new(FaFamily::Jelly, FaBaseStyle::Regular),
+        Self::new(FaFamily::JellyDuo, FaBaseStyle::Regular),
+        Self::new(FaFamily::JellyFill, FaBaseStyle::Regular),
+        Self::new(FaFamily::Notdog, FaBaseStyle::Solid),
+        Self::new(FaFamily::NotdogDuo, FaBaseStyle::Solid),
+        Self::new(FaFamily::Slab, FaBaseStyle::Regular),
+        Self::new(FaFamily::SlabPress, FaBaseStyle::Regular),
+        Self::new(FaFamily::Thumbprint, FaBaseStyle::Light),
+        Self::new(FaFamily::Whiteboard, FaBaseStyle::Semibold),
+        Self::new(FaFamily::Utility, FaBaseStyle::Semibold),
+        Self::new(FaFamily::UtilityDuo, FaBaseStyle::Semibold),
+        Self::new(FaFamily::UtilityFill, FaBaseStyle::Semibold),
+    ];
+
+    /// The only variant brand icons exist in.
+    pub const BRANDS: FaVariant = Self::new(FaFamily::Classic, FaBaseStyle::Brands);
+
+    pub const fn new(family: FaFamily, base: FaBaseStyle) -> Self {
+        Self { family, base }
+    }
+
+    pub const fn is_sharp(&self) -> bool {
+        self.family.is_sharp()
+    }
+
+    pub const fn is_duotone(&self) -> bool {
+        self.family.is_duotone()
+    }
+
+    /// This variant's weight in `family`.
+    pub const fn with_family(mut self, family: FaFamily) -> Self {
+        self.family = family;
+        self
+    }
+
+    /// This variant's family in the weight `base`.
+    pub const fn with_base(mut self, base: FaBaseStyle) -> Self {
+        self.base = base;
+        self
+    }
+
+    /// Checks that Font Awesome ships this combination.
+    pub fn validate(&self) -> Result<(), FaVariantError> {
+        if self.base == FaBaseStyle::Brands && self.family != FaFamily::Classic {
+            return Err(FaVariantError::BrandsModifier(*self));
+        }
+        if self.index().is_none() {
+            return Err(FaVariantError::Unavailable(*self));
+        }
+        Ok(())
+    }
+
+    /// Whether svgs of this variant get embedded, according to the crate's Cargo features.
+    ///
+    /// A variant is embedded when both its family (`sharp`, `duotone`, `sharp-duotone`,
+    /// `brands`, `chisel`, `etch`, `jelly`, `notdog`, `slab`, `thumbprint`, `whiteboard`,
+    /// `utility`, with the classic family always on) and its weight (`solid`, `regular`,
+    /// `light`, `thin`, `semibold`) are enabled. Icons asked for in a variant that isn't
+    /// embedded render according to their [`FaFallback`].
+    pub const fn is_embedded(&self) -> bool {
+        if let FaBaseStyle::Brands = self.base {
+            return matches!(self.family, FaFamily::Classic) && cfg!(feature = "brands");
+        }
+        let family = match self.family {
+            FaFamily::Classic => true,
+            FaFamily::Sharp => cfg!(feature = "sharp"),
+            FaFamily::Duotone => cfg!(feature = "duotone"),
+            FaFamily::SharpDuotone => cfg!(feature = "sharp-duotone"),
+            FaFamily::Chisel => cfg!(feature = "chisel"),
+            FaFamily::Etch => cfg!(feature = "etch"),
+            FaFamily::Jelly | FaFamily::JellyDuo | FaFamily::JellyFill => cfg!(feature = "jelly"),
+            FaFamily::Notdog | FaFamily::NotdogDuo => cfg!(feature = "notdog"),
+            FaFamily::Slab | FaFamily::SlabPress => cfg!(feature = "slab"),
+            FaFamily::Thumbprint => cfg!(feature = "thumbprint"),
+            FaFamily::Whiteboard => cfg!(feature = "whiteboard"),
+            FaFamily::Utility | FaFamily::UtilityDuo | FaFamily::UtilityFill => {
+                cfg!(feature = "utility")
+            }
+        };
+        let weight = match self.base {
+            FaBaseStyle::Solid => cfg!(feature = "solid"),
+            FaBaseStyle::Regular => cfg!(feature = "regular"),
+            FaBaseStyle::Light => cfg!(feature = "light"),
+            FaBaseStyle::Thin => cfg!(feature = "thin"),
+            FaBaseStyle::Semibold => cfg!(feature = "semibold"),
+            FaBaseStyle::Brands => unreachable!(),
+        };
+        family && weight
+    }
+
+    /// Position of this variant in [`FaVariant::ALL`], or `None` for combinations that
+    /// don't exist.
+    #[doc(hidden)]
+    pub const fn index(&self) -> Option<usize> {
+        let (family, base) = (self.family as usize, self.base as usize);
+        if family < VARIANT_INDEX.len() {
+            VARIANT_INDEX[family][base]
+        } else {
+            None
+        }
+    }
+
+    /// Directory name of this variant inside a Font Awesome `svgs/` tree, which is where
+    /// `fa_icon_build!` and the generator load it from, or `None` for combinations that
+    /// don't exist.
+    pub fn dir_name(&self) -> Option<String> {
+        self.index().map(|_| self.to_string())
     }
 }
 
-impl StylePropValue for FaVariant {}
+/// A [`FaVariant`] combination that Font Awesome doesn't ship.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FaVariantError {
+    /// Brand icons have no sharp, duotone or other family versions.
+    BrandsModifier(FaVariant),
+    /// The family doesn't come in this style, e.g. `chisel-thin`.
+    Unavailable(FaVariant),
+}
+
+impl std::fmt::Display for FaVariantError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FaVariantError::BrandsModifier(variant) => write!(
+                f,
+                "`{variant}` does not exist: brand icons have no sharp or duotone versions"
+            ),
+            FaVariantError::Unavailable(variant) => write!(
+                f,
+                "`{variant}` does not exist: the {} family has no {} style",
+                variant.family, variant.base
+            ),
+        }
+    }
+}
+
+impl std::error::Error for FaVariantError {}
 
-prop!(pub FaVariantProp: FaVariant {} = FaVariant::default());
+/// A string that doesn't name a [`FaVariant`] or [`FaBaseStyle`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FaUnknownVariantError {
+    pub name: String,
+}
+
+impl std::fmt::Display for FaUnknownVariantError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unknown Font Awesome style `{}`", self.name)
+    }
+}
+
+impl std::error::Error for FaUnknownVariantError {}
+
+/// A name that isn't part of an icon set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FaUnknownIconError {
+    pub name: String,
+}
+
+impl std::fmt::Display for FaUnknownIconError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unknown Font Awesome icon `{}`", self.name)
+    }
+}
+
+impl std::error::Error for FaUnknownIconError {}
+
+impl StylePropValue for FaFamily {}
+impl StylePropValue for FaBaseStyle {}
+
+// The variant is split into separate props so that a style setting one part, e.g. the
+// weight, leaves the parts set by a parent's class style in place
+prop!(pub FaFamilyProp: FaFamily {} = FaFamily::Classic);
+prop!(pub FaBaseStyleProp: FaBaseStyle {} = FaBaseStyle::Solid);
+
+// Sharp and duotone modifiers, applied on top of the family when set
+prop!(pub FaSharpProp: Option<bool> {} = None);
+prop!(pub FaDuotoneProp: Option<bool> {} = None);
+
+/// The variant the family, style and modifier props resolve to.
+fn variant_from_props(
+    family: FaFamily,
+    base: FaBaseStyle,
+    sharp: Option<bool>,
+    duotone: Option<bool>,
+) -> FaVariant {
+    let family = sharp.map_or(family, |sharp| family.with_sharp(sharp));
+    let family = duotone.map_or(family, |duotone| family.with_duotone(duotone));
+    FaVariant::new(family, base)
+}
 
 // Property for color (for non-duotone variants)
 prop!(pub FaColor: Option<Brush> {} = None);
@@ -73,8 +561,222 @@ prop!(pub FaColor: Option<Brush> {} = None);
 prop!(pub FaPrimary: Option<Brush> {} = None);
 prop!(pub FaSecondary: Option<Brush> {} = None);
 
+// Duotone layer opacities, Font Awesome's defaults
+prop!(pub FaPrimaryOpacity: f32 {} = 1.0);
+prop!(pub FaSecondaryOpacity: f32 {} = 0.4);
+
+// Whether the duotone layers trade opacities, as with `fa-swap-opacity`
+prop!(pub FaSwapOpacity: bool {} = false);
+
+// What to render when the icon lacks the requested variant
+prop!(pub FaFallbackProp: FaFallback {} = FaFallback::Global);
+
+// Size relative to the inherited font size
+prop!(pub FaSizeProp: Option<FaSize> {} = None);
+
+// Whether the icon is as wide as `fa-fw` rather than as its svg
+prop!(pub FaFixedWidth: bool {} = false);
+
+/// Width of fixed width icons relative to their height, as with Font Awesome's `fa-fw`.
+const FIXED_WIDTH: f64 = 1.25;
+
+// Whether the icon is shifted down to sit on the baseline of adjacent text
+prop!(pub FaAlignText: bool {} = false);
+
+/// How far Font Awesome's CSS lowers icons below the text's box, relative to their height.
+const TEXT_OFFSET: f64 = 0.125;
+
+/// Font size floem renders text at when none is set.
+const DEFAULT_FONT_SIZE: f32 = 14.0;
+
+const EMPTY_SVG: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24"></svg>"#;
+
 pub trait FaIconTrait {
-    fn svg(&self, variant: FaVariant) -> &'static str;
+    /// Font Awesome name of the icon, e.g. `arrow-right` for `ArrowRight` or `42-group` for
+    /// the mangled `N42Group`.
+    fn name(&self) -> &'static str;
+
+    /// The embedded svg for `variant`, or `None` if that file wasn't available at build time.
+    fn try_svg(&self, variant: FaVariant) -> Option<&'static str>;
+
+    /// The embedded svg for `variant`, or an empty svg if it isn't available.
+    fn svg(&self, variant: FaVariant) -> &'static str {
+        self.try_svg(variant).unwrap_or(EMPTY_SVG)
+    }
+
+    fn has_variant(&self, variant: FaVariant) -> bool {
+        self.try_svg(variant).is_some()
+    }
+
+    /// Whether this is a brand icon, which only exists in the `brands` style.
+    fn is_brand(&self) -> bool {
+        self.has_variant(FaVariant::BRANDS)
+            && FaVariant::ALL
+                .iter()
+                .copied()
+                .all(|variant| variant == FaVariant::BRANDS || !self.has_variant(variant))
+    }
+
+    /// The variant rendered for `requested` under `fallback`, or `None` if no candidate
+    /// is available. Brand icons always render as [`FaVariant::BRANDS`].
+    fn resolve_variant(&self, requested: FaVariant, fallback: &FaFallback) -> Option<FaVariant> {
+        if self.is_brand() {
+            return Some(FaVariant::BRANDS);
+        }
+        fallback
+            .candidates(requested)
+            .into_iter()
+            .find(|variant| self.has_variant(*variant))
+    }
+
+    /// All variants this icon was embedded in.
+    fn variants(&self) -> Vec<FaVariant> {
+        FaVariant::ALL
+            .iter()
+            .copied()
+            .filter(|variant| self.has_variant(*variant))
+            .collect()
+    }
+
+    /// Font Awesome's metadata about this icon, when the icon set shipped a `metadata/`
+    /// directory that lists it and it was read, see the `metadata` argument of
+    /// [`fa_icons`].
+    fn info(&self) -> Option<&'static FaIconInfo> {
+        None
+    }
+
+    /// This icon fixed to `variant`, see [`fa_icon_with`].
+    fn with_variant(self, variant: FaVariant) -> FaIconVariant<Self>
+    where
+        Self: Sized,
+    {
+        FaIconVariant {
+            icon: self,
+            variant,
+        }
+    }
+
+    fn solid(self) -> FaIconVariant<Self>
+    where
+        Self: Sized,
+    {
+        self.with_variant(FaVariant::default()).solid()
+    }
+
+    fn regular(self) -> FaIconVariant<Self>
+    where
+        Self: Sized,
+    {
+        self.with_variant(FaVariant::default()).regular()
+    }
+
+    fn light(self) -> FaIconVariant<Self>
+    where
+        Self: Sized,
+    {
+        self.with_variant(FaVariant::default()).light()
+    }
+
+    fn thin(self) -> FaIconVariant<Self>
+    where
+        Self: Sized,
+    {
+        self.with_variant(FaVariant::default()).thin()
+    }
+
+    fn semibold(self) -> FaIconVariant<Self>
+    where
+        Self: Sized,
+    {
+        self.with_variant(FaVariant::default()).semibold()
+    }
+
+    fn brands(self) -> FaIconVariant<Self>
+    where
+        Self: Sized,
+    {
+        self.with_variant(FaVariant::BRANDS)
+    }
+
+    /// The sharp version of this icon, solid unless another style is chained:
+    /// `Icon::House.sharp().regular()`.
+    fn sharp(self) -> FaIconVariant<Self>
+    where
+        Self: Sized,
+    {
+        self.with_variant(FaVariant::default()).sharp()
+    }
+
+    /// The duotone version of this icon, solid unless another style is chained.
+    fn duotone(self) -> FaIconVariant<Self>
+    where
+        Self: Sized,
+    {
+        self.with_variant(FaVariant::default()).duotone()
+    }
+}
+
+/// An icon fixed to a variant, built with [`FaIconTrait::with_variant`] or the style
+/// shorthands like `Icon::House.sharp().regular()`. Turns into a [`fa_icon_with`] view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FaIconVariant<T> {
+    pub icon: T,
+    pub variant: FaVariant,
+}
+
+impl<T> FaIconVariant<T> {
+    pub fn solid(mut self) -> Self {
+        self.variant.base = FaBaseStyle::Solid;
+        self
+    }
+
+    pub fn regular(mut self) -> Self {
+        self.variant.base = FaBaseStyle::Regular;
+        self
+    }
+
+    pub fn light(mut self) -> Self {
+        self.variant.base = FaBaseStyle::Light;
+        self
+    }
+
+    pub fn thin(mut self) -> Self {
+        self.variant.base = FaBaseStyle::Thin;
+        self
+    }
+
+    pub fn semibold(mut self) -> Self {
+        self.variant.base = FaBaseStyle::Semibold;
+        self
+    }
+
+    pub fn brands(mut self) -> Self {
+        self.variant.base = FaBaseStyle::Brands;
+        self
+    }
+
+    pub fn family(mut self, family: FaFamily) -> Self {
+        self.variant.family = family;
+        self
+    }
+
+    pub fn sharp(mut self) -> Self {
+        self.variant.family = self.variant.family.with_sharp(true);
+        self
+    }
+
+    pub fn duotone(mut self) -> Self {
+        self.variant.family = self.variant.family.with_duotone(true);
+        self
+    }
+}
+
+impl<T: FaIconTrait + 'static> floem::IntoView for FaIconVariant<T> {
+    type V = FaIcon<T>;
+
+    fn into_view(self) -> Self::V {
+        fa_icon_with(self.icon, self.variant)
+    }
 }
 
 pub struct FaIcon<T> {
@@ -82,28 +784,170 @@ pub struct FaIcon<T> {
     svg_id: ViewId,
     style: FaStyleExt,
     icon: T,
+    /// Variant passed to [`fa_icon_with`], overriding the style's.
+    explicit: Option<FaVariant>,
     variant: FaVariant,
+    /// Natural layout from the style and the inherited font size.
+    icon_box: IconBox,
+    /// Width over height of the current svg, which the inner svg view keeps while centered
+    /// in this view.
+    aspect_ratio: RwSignal<f32>,
+}
+
+/// Natural layout of a [`FaIcon`], in pixels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct IconBox {
+    /// As wide as the svg's viewBox proportions allow, or 1.25 times the height with
+    /// [`FaFixedWidth`].
+    width: f64,
+    /// [`FaSizeProp`] times the inherited font size.
+    height: f64,
+    /// Downward shift from [`FaAlignText`].
+    offset: f64,
+}
+
+impl IconBox {
+    /// Lays out an svg `aspect_ratio` wide per unit of height at `font_size` pixels.
+    fn new(
+        font_size: f64,
+        size: Option<FaSize>,
+        aspect_ratio: f64,
+        fixed_width: bool,
+        align_text: bool,
+    ) -> Self {
+        let height = size.map_or(1.0, |size| size.scale()) * font_size;
+        let width = if fixed_width {
+            FIXED_WIDTH * height
+        } else {
+            aspect_ratio * height
+        };
+        let offset = if align_text {
+            TEXT_OFFSET * height
+        } else {
+            0.0
+        };
+        IconBox {
+            width,
+            height,
+            offset,
+        }
+    }
+}
+
+impl<T: FaIconTrait> FaIcon<T> {
+    /// Resolves the variant for the current icon and style and hands its svg and css to the
+    /// inner svg view.
+    fn update_svg(&mut self) {
+        let requested = self.explicit.unwrap_or_else(|| self.style.variant());
+        self.variant = self
+            .icon
+            .resolve_variant(requested, &self.style.fallback())
+            .unwrap_or(requested);
+        let svg = self.icon.svg(self.variant);
+        self.svg_id.update_state(SvgOrStyle::Svg(svg.to_string()));
+        self.svg_id
+            .update_state(SvgOrStyle::Style(self.style.css_for(self.variant)));
+        self.update_size(size::aspect_ratio(svg).unwrap_or(1.0));
+    }
+
+    /// Swaps in the icon sent by [`fa_icon_dyn`], ignoring state of any other type.
+    fn set_icon(&mut self, state: Box<dyn std::any::Any>)
+    where
+        T: 'static,
+    {
+        if let Ok(icon) = state.downcast::<T>() {
+            self.icon = *icon;
+            self.update_svg();
+        }
+    }
+
+    /// Recomputes the natural size for an svg `aspect_ratio` wide per unit of height.
+    ///
+    /// The size depends on the inherited font size, which is only known once this view is
+    /// styled, so a change restyles it with the new `view_style`.
+    fn update_size(&mut self, aspect_ratio: f64) {
+        if self.aspect_ratio.get_untracked() != aspect_ratio as f32 {
+            self.aspect_ratio.set(aspect_ratio as f32);
+        }
+        let icon_box = IconBox::new(
+            f64::from(self.style.font_size().unwrap_or(DEFAULT_FONT_SIZE)),
+            self.style.size(),
+            aspect_ratio,
+            self.style.fixed_width(),
+            self.style.align_text(),
+        );
+        if icon_box != self.icon_box {
+            self.icon_box = icon_box;
+            self.id.request_style();
+        }
+    }
 }
-impl<T: FaIconTrait> View for FaIcon<T> {
+
+impl<T: FaIconTrait + 'static> View for FaIcon<T> {
     fn id(&self) -> ViewId {
         self.id
     }
+    fn view_style(&self) -> Option<Style> {
+        let icon_box = self.icon_box;
+        let style = Style::new()
+            .size(icon_box.width, icon_box.height)
+            .items_center()
+            .justify_center();
+        Some(if icon_box.offset != 0.0 {
+            style.translate_y(icon_box.offset)
+        } else {
+            style
+        })
+    }
     fn style_pass(&mut self, cx: &mut floem::context::StyleCx<'_>) {
         if self.style.read(cx) {
-            self.variant = self.style.variant();
-            self.svg_id
-                .update_state(SvgOrStyle::Svg(self.icon.svg(self.variant).to_string()));
-            self.svg_id
-                .update_state(SvgOrStyle::Style(self.style.css_string()));
+            self.update_svg();
         }
         cx.style_view(self.svg_id);
     }
+    fn update(&mut self, _cx: &mut floem::context::UpdateCx, state: Box<dyn std::any::Any>) {
+        self.set_icon(state);
+    }
 }
 pub fn fa_icon<T: FaIconTrait + 'static>(icon: T) -> FaIcon<T> {
+    fa_icon_with_id(ViewId::new(), icon, None)
+}
+
+/// An icon rendered in `variant` regardless of its style.
+///
+/// The explicit variant takes precedence over the style's variant, whether that was set with
+/// [`FaStyle::sharp`] and friends, by [`FaClasses::style`] or inherited from a parent. Colors
+/// and [`FaFallbackProp`] still come from the style, so an icon lacking `variant` falls back
+/// as usual.
+pub fn fa_icon_with<T: FaIconTrait + 'static>(icon: T, variant: FaVariant) -> FaIcon<T> {
+    fa_icon_with_id(ViewId::new(), icon, Some(variant))
+}
+
+/// An icon that follows a reactive computation, e.g.
+/// `fa_icon_dyn(move || if playing.get() { Icon::Pause } else { Icon::Play })`.
+///
+/// The svg is swapped in place whenever the computation changes, so the view keeps its
+/// styles, size and position in the tree.
+pub fn fa_icon_dyn<T: FaIconTrait + 'static>(icon: impl Fn() -> T + 'static) -> FaIcon<T> {
     let id = ViewId::new();
-    let variant = FaVariant::default();
+    let icon = create_updater(icon, move |icon| id.update_state(icon));
+    fa_icon_with_id(id, icon, None)
+}
+
+fn fa_icon_with_id<T: FaIconTrait + 'static>(
+    id: ViewId,
+    icon: T,
+    explicit: Option<FaVariant>,
+) -> FaIcon<T> {
+    let requested = explicit.unwrap_or_default();
+    let variant = icon
+        .resolve_variant(requested, &FaFallback::Global)
+        .unwrap_or(requested);
     let svg = icon.svg(variant);
-    let svg = floem::views::svg(svg).style(|s| s.size_full());
+    let natural_ratio = size::aspect_ratio(svg).unwrap_or(1.0);
+    let aspect_ratio = RwSignal::new(natural_ratio as f32);
+    let svg =
+        floem::views::svg(svg).style(move |s| s.height_full().aspect_ratio(aspect_ratio.get()));
     let svg_id = svg.id();
     id.set_children([svg]);
     FaIcon {
@@ -111,7 +955,17 @@ pub fn fa_icon<T: FaIconTrait + 'static>(icon: T) -> FaIcon<T> {
         svg_id,
         style: Default::default(),
         icon,
+        explicit,
         variant,
+        // Until a style differs from the defaults, the icon is 1em at the default font size
+        icon_box: IconBox::new(
+            f64::from(DEFAULT_FONT_SIZE),
+            None,
+            natural_ratio,
+            false,
+            false,
+        ),
+        aspect_ratio,
     }
     .class(FaClass)
 }
@@ -120,10 +974,21 @@ style_class!(pub FaClass);
 
 prop_extractor! {
     pub FaStyleExt {
-        variant: FaVariantProp,
+        family: FaFamilyProp,
+        base: FaBaseStyleProp,
+        sharp: FaSharpProp,
+        duotone: FaDuotoneProp,
         color: FaColor,
         primary: FaPrimary,
         secondary: FaSecondary,
+        primary_opacity: FaPrimaryOpacity,
+        secondary_opacity: FaSecondaryOpacity,
+        swap_opacity: FaSwapOpacity,
+        fallback: FaFallbackProp,
+        size: FaSizeProp,
+        fixed_width: FaFixedWidth,
+        align_text: FaAlignText,
+        font_size: FontSize,
     }
 }
 
@@ -133,8 +998,19 @@ impl SvgCssPropExtractor for FaStyleExt {
     }
 
     fn css_string(&self) -> String {
-        let variant = self.variant();
+        self.css_for(self.variant())
+    }
+}
+
+impl FaStyleExt {
+    /// The variant requested by the style.
+    pub fn variant(&self) -> FaVariant {
+        variant_from_props(self.family(), self.base(), self.sharp(), self.duotone())
+    }
 
+    /// Css for the svg of `variant`, which may differ from the requested variant when the
+    /// icon had to fall back.
+    pub fn css_for(&self, variant: FaVariant) -> String {
         if variant.is_duotone() {
             // Duotone styling
             let primary_color = match self.primary() {
@@ -145,17 +1021,24 @@ impl SvgCssPropExtractor for FaStyleExt {
                 Some(ref brush) => brush_to_css_string(brush),
                 None => "currentColor".to_string(),
             };
+            let (mut primary_opacity, mut secondary_opacity) =
+                (self.primary_opacity(), self.secondary_opacity());
+            if self.swap_opacity() {
+                std::mem::swap(&mut primary_opacity, &mut secondary_opacity);
+            }
 
             format!(
                 r#"
                     .fa-primary {{
                       fill: {} !important;
+                      opacity: {} !important;
                     }}
                     .fa-secondary {{
                       fill: {} !important;
+                      opacity: {} !important;
                     }}
                 "#,
-                primary_color, secondary_color
+                primary_color, primary_opacity, secondary_color, secondary_opacity
             )
         } else {
             // For non-duotone variants, use single color
@@ -197,55 +1080,171 @@ impl CustomStyle for FaStyle {
     type StyleClass = FaClass;
 }
 
+/// The variant and colors of a [`FaStyle`], detached from floem's style system so it can be
+/// stored, e.g. as part of a saved theme with the `serde` feature.
+///
+/// Only solid colors are captured; gradient and image brushes are left out.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FaStyleSnapshot {
+    pub variant: FaVariant,
+    #[cfg_attr(feature = "serde", serde(default, with = "serde_impls::color"))]
+    pub color: Option<Color>,
+    #[cfg_attr(feature = "serde", serde(default, with = "serde_impls::color"))]
+    pub primary: Option<Color>,
+    #[cfg_attr(feature = "serde", serde(default, with = "serde_impls::color"))]
+    pub secondary: Option<Color>,
+}
+
+impl From<FaStyleSnapshot> for FaStyle {
+    fn from(snapshot: FaStyleSnapshot) -> Self {
+        let color = |color: Option<Color>| color.map(Brush::Solid);
+        Self(
+            Style::new()
+                .set(FaColor, color(snapshot.color))
+                .set(FaPrimary, color(snapshot.primary))
+                .set(FaSecondary, color(snapshot.secondary)),
+        )
+        .variant(snapshot.variant)
+    }
+}
+
 impl FaStyle {
+    /// Captures the variant and colors set on this style.
+    pub fn snapshot(&self) -> FaStyleSnapshot {
+        let color = |brush: Option<Brush>| match brush {
+            Some(Brush::Solid(color)) => Some(color),
+            _ => None,
+        };
+        FaStyleSnapshot {
+            variant: self.get_variant(),
+            color: color(self.0.get(FaColor)),
+            primary: color(self.0.get(FaPrimary)),
+            secondary: color(self.0.get(FaSecondary)),
+        }
+    }
+
     // Base styles
     pub fn solid(mut self) -> Self {
-        let mut var = self.0.get(FaVariantProp);
-        var.base = FaBaseStyle::Solid;
-        self = Self(self.0.set(FaVariantProp, var));
+        self = Self(self.0.set(FaBaseStyleProp, FaBaseStyle::Solid));
         self
     }
 
     pub fn regular(mut self) -> Self {
-        let mut var = self.0.get(FaVariantProp);
-        var.base = FaBaseStyle::Regular;
-        self = Self(self.0.set(FaVariantProp, var));
+        self = Self(self.0.set(FaBaseStyleProp, FaBaseStyle::Regular));
         self
     }
 
     pub fn light(mut self) -> Self {
-        let mut var = self.0.get(FaVariantProp);
-        var.base = FaBaseStyle::Light;
-        self = Self(self.0.set(FaVariantProp, var));
+        self = Self(self.0.set(FaBaseStyleProp, FaBaseStyle::Light));
         self
     }
 
     pub fn thin(mut self) -> Self {
-        let mut var = self.0.get(FaVariantProp);
-        var.base = FaBaseStyle::Thin;
-        self = Self(self.0.set(FaVariantProp, var));
+        self = Self(self.0.set(FaBaseStyleProp, FaBaseStyle::Thin));
+        self
+    }
+
+    pub fn semibold(mut self) -> Self {
+        self = Self(self.0.set(FaBaseStyleProp, FaBaseStyle::Semibold));
         self
     }
 
+    /// Brand icons have no sharp or duotone versions; combining this with
+    /// [`FaStyle::sharp`] or [`FaStyle::duotone`] fails [`FaVariant::validate`] and renders
+    /// according to the icon's [`FaFallback`].
     pub fn brands(mut self) -> Self {
-        let mut var = self.0.get(FaVariantProp);
-        var.base = FaBaseStyle::Brands;
-        self = Self(self.0.set(FaVariantProp, var));
+        self = Self(self.0.set(FaBaseStyleProp, FaBaseStyle::Brands));
         self
     }
 
     // Modifiers
     pub fn sharp(mut self) -> Self {
-        let mut var = self.0.get(FaVariantProp);
-        var.is_sharp = true;
-        self = Self(self.0.set(FaVariantProp, var));
+        self = Self(self.0.set(FaSharpProp, Some(true)));
         self
     }
 
+    /// Switches to the family's two-tone version, e.g. `jelly-duo` for `jelly`.
     pub fn duotone(mut self) -> Self {
-        let mut var = self.0.get(FaVariantProp);
-        var.is_duotone = true;
-        self = Self(self.0.set(FaVariantProp, var));
+        self = Self(self.0.set(FaDuotoneProp, Some(true)));
+        self
+    }
+
+    /// Clears the sharp modifier, e.g. one set by a parent's class.
+    pub fn not_sharp(mut self) -> Self {
+        self = Self(self.0.set(FaSharpProp, Some(false)));
+        self
+    }
+
+    /// Clears the duotone modifier, e.g. one set by a parent's class.
+    pub fn not_duotone(mut self) -> Self {
+        self = Self(self.0.set(FaDuotoneProp, Some(false)));
+        self
+    }
+
+    /// Replaces the family, keeping the weight. Sharp and duotone modifiers, including
+    /// ones set by a parent's class, no longer apply.
+    pub fn family(mut self, family: FaFamily) -> Self {
+        self = Self(
+            self.0
+                .set(FaFamilyProp, family)
+                .set(FaSharpProp, None)
+                .set(FaDuotoneProp, None),
+        );
+        self
+    }
+
+    /// Replaces the whole variant.
+    pub fn variant(mut self, variant: FaVariant) -> Self {
+        self = self.family(variant.family);
+        self = Self(self.0.set(FaBaseStyleProp, variant.base));
+        self
+    }
+
+    /// Checks that the variant set on this style is one Font Awesome ships, see
+    /// [`FaVariant::validate`].
+    pub fn validate(&self) -> Result<(), FaVariantError> {
+        self.get_variant().validate()
+    }
+
+    /// The variant set on this style, without anything it would inherit.
+    fn get_variant(&self) -> FaVariant {
+        variant_from_props(
+            self.0.get(FaFamilyProp),
+            self.0.get(FaBaseStyleProp),
+            self.0.get(FaSharpProp),
+            self.0.get(FaDuotoneProp),
+        )
+    }
+
+    /// Sizes the icon on Font Awesome's scale, relative to the inherited font size.
+    /// Without a size icons are 1em high. An explicit width or height in the icon's style
+    /// takes precedence.
+    pub fn size(mut self, size: FaSize) -> Self {
+        self = Self(self.0.set(FaSizeProp, Some(size)));
+        self
+    }
+
+    /// Makes the icon 1.25 times as wide as it is high with the glyph centered, like Font
+    /// Awesome's `fa-fw`, so icons of different widths line up in a column.
+    pub fn fixed_width(mut self) -> Self {
+        self = Self(self.0.set(FaFixedWidth, true));
+        self
+    }
+
+    /// Makes the icon as wide as its svg, undoing a [`FaStyle::fixed_width`] from a class.
+    pub fn auto_width(mut self) -> Self {
+        self = Self(self.0.set(FaFixedWidth, false));
+        self
+    }
+
+    /// Lowers the icon by 0.125em, the `vertical-align` Font Awesome's CSS uses, so it sits
+    /// optically centered on the baseline of text next to it in a row.
+    ///
+    /// floem's layout has no baselines to align to, so this shifts the painted icon
+    /// without moving its layout box, like a relative offset on the web.
+    pub fn align_text(mut self) -> Self {
+        self = Self(self.0.set(FaAlignText, true));
         self
     }
 
@@ -265,6 +1264,38 @@ impl FaStyle {
         self
     }
 
+    /// Opacity of the primary duotone layer, 1 by default.
+    pub fn primary_opacity(mut self, opacity: f32) -> Self {
+        self = Self(self.0.set(FaPrimaryOpacity, opacity));
+        self
+    }
+
+    /// Opacity of the secondary duotone layer, 0.4 by default.
+    pub fn secondary_opacity(mut self, opacity: f32) -> Self {
+        self = Self(self.0.set(FaSecondaryOpacity, opacity));
+        self
+    }
+
+    /// Gives the primary layer the secondary opacity and the other way around, like Font
+    /// Awesome's `fa-swap-opacity`.
+    pub fn swap_opacity(mut self) -> Self {
+        self = Self(self.0.set(FaSwapOpacity, true));
+        self
+    }
+
+    /// Keeps the layer opacities in place, undoing a [`FaStyle::swap_opacity`] from a
+    /// class.
+    pub fn unswap_opacity(mut self) -> Self {
+        self = Self(self.0.set(FaSwapOpacity, false));
+        self
+    }
+
+    /// What to render when the icon lacks the requested variant.
+    pub fn fallback(mut self, fallback: FaFallback) -> Self {
+        self = Self(self.0.set(FaFallbackProp, fallback));
+        self
+    }
+
     // Transitions
     pub fn transition_color(mut self, transition: Transition) -> Self {
         self = Self(self.0.transition(FaColor, transition));
@@ -280,225 +1311,524 @@ impl FaStyle {
         self = Self(self.0.transition(FaSecondary, transition));
         self
     }
+
+    pub fn transition_primary_opacity(mut self, transition: Transition) -> Self {
+        self = Self(self.0.transition(FaPrimaryOpacity, transition));
+        self
+    }
+
+    pub fn transition_secondary_opacity(mut self, transition: Transition) -> Self {
+        self = Self(self.0.transition(FaSecondaryOpacity, transition));
+        self
+    }
+}
+
+/// Svg table cell for column `index` of `FaVariant::ALL`. Variants disabled through Cargo
+/// features evaluate to `None`, which keeps their svgs out of the binary.
+#[doc(hidden)]
+pub const fn __fa_embed(index: usize, svg: &'static str) -> Option<&'static str> {
+    if FaVariant::ALL[index].is_embedded() {
+        Some(svg)
+    } else {
+        None
+    }
+}
+
+/// Looks `name` up among `icons` by Font Awesome name, then by alias, ignoring ASCII case
+/// and an `fa-` prefix.
+#[doc(hidden)]
+pub fn __fa_from_str<T: FaIconTrait + Copy>(
+    name: &str,
+    icons: &[T],
+) -> Result<T, FaUnknownIconError> {
+    let trimmed = name.trim();
+    let unprefixed = trimmed.strip_prefix("fa-");
+    let is_name = |candidate: &str| {
+        candidate.eq_ignore_ascii_case(trimmed)
+            || unprefixed.is_some_and(|name| candidate.eq_ignore_ascii_case(name))
+    };
+    icons
+        .iter()
+        .find(|icon| is_name(icon.name()))
+        .or_else(|| {
+            icons.iter().find(|icon| {
+                icon.info()
+                    .is_some_and(|info| info.aliases.iter().any(|alias| is_name(alias)))
+            })
+        })
+        .copied()
+        .ok_or_else(|| FaUnknownIconError {
+            name: name.to_string(),
+        })
 }
 
+pub use floem_fontawesome_macros::fa_icons;
+
+/// Declares an icon enum whose svgs are embedded from `$base_path`.
+///
+/// Each variant is loaded from `<base_path>/<style>/<file stem>.svg`, where the file stem is
+/// the kebab-case form of the variant name (`ArrowRight` loads `arrow-right.svg`). Write
+/// `Variant = "file-stem"` for icons whose name can't be derived that way.
+///
+/// Files that don't exist are skipped instead of failing the build, so partial icon sets
+/// such as Font Awesome Free compile; use [`FaIconTrait::variants`] to see what was embedded.
+/// Skipped files aren't tracked, so one added later only shows up once the invoking source
+/// file changes. Touch it, or generate the enum from a build script with
+/// `floem_fontawesome_codegen::Generator` to have Cargo watch the whole directory.
+/// Styles disabled through Cargo features are left out as well, see
+/// [`FaVariant::is_embedded`].
+/// An icon that exists in no style at all is a compile error. Relative base paths are
+/// resolved against the invoking source file.
+///
+/// Names that aren't valid identifiers follow the generator's mangling scheme and are
+/// mapped back automatically: `Digit0` loads `0.svg`, `N42Group` loads `42-group.svg` and
+/// `Type_` loads `type.svg`.
+///
+/// This is shorthand for the [`fa_icons`] attribute, see there for the traits the enum
+/// implements. It doesn't read the icon set's metadata, so [`FaIconTrait::info`] is `None`
+/// and parsing doesn't know about aliases; use `#[fa_icons(.., metadata = true)]` for that.
 #[macro_export]
 macro_rules! fa_icon_build {
     ($base_path:expr, $public:vis enum $name:ident {
-        $($variants:tt)*
+        $($variant:ident $(= $path:expr)?),* $(,)?
     }) => {
-        use $crate::extract_enum_variants;
+        #[$crate::fa_icons(path = $base_path, crate = $crate)]
+        $public enum $name {
+            $($variant $(= $path)?),*
+        }
+    }
+}
 
-        extract_enum_variants!{[$($variants)*], $public, $name}
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        impl floem::IntoView for $name {
-            type V = $crate::FaIcon<$name>;
+    #[test]
+    fn test_macros() {
+        fa_icon_build! {"", enum Icon {}};
+    }
 
-            fn into_view(self) -> Self::V {
-                $crate::fa_icon(self)
-            }
+    #[test]
+    fn test_empty_enum() {
+        #[fa_icons(path = "", crate = crate)]
+        enum Empty {}
+
+        assert!(Empty::ALL.is_empty());
+        assert_eq!(std::mem::size_of::<Empty>(), 0);
+    }
+
+    #[test]
+    #[cfg(all(feature = "solid", feature = "regular"))]
+    fn test_missing_variant_files() {
+        fa_icon_build! {"../tests/fixtures/svgs", enum Icon { House, ArrowRight, }};
+
+        let solid = FaVariant::default();
+        let regular = FaVariant::new(FaFamily::Classic, FaBaseStyle::Regular);
+        assert_eq!(Icon::House.variants(), vec![solid, regular]);
+        assert_eq!(Icon::ArrowRight.variants(), vec![solid]);
+        assert!(Icon::ArrowRight.try_svg(regular).is_none());
+        assert!(
+            !Icon::ArrowRight
+                .has_variant(FaVariant::new(FaFamily::SharpDuotone, FaBaseStyle::Thin))
+        );
+        assert!(
+            Icon::ArrowRight
+                .svg(solid)
+                .contains("viewBox=\"0 0 448 512\"")
+        );
+    }
+
+    #[test]
+    fn test_fa_icons_attribute() {
+        /// Doc comments are kept.
+        #[fa_icons(path = "../tests/fixtures/svgs", require = ["solid"], crate = crate)]
+        #[repr(u16)]
+        enum Icon {
+            House,
+            Home = "house",
         }
 
-        impl $crate::FaIconTrait for $name {
-            fn svg(&self, variant: $crate::FaVariant) -> &'static str {
-                use $crate::{FaVariant, FaBaseStyle};
-
-                // Get the variant string for file path
-                let variant_path = match variant {
-                    FaVariant { base: FaBaseStyle::Solid, is_sharp: false, is_duotone: false } => "solid",
-                    FaVariant { base: FaBaseStyle::Regular, is_sharp: false, is_duotone: false } => "regular",
-                    FaVariant { base: FaBaseStyle::Light, is_sharp: false, is_duotone: false } => "light",
-                    FaVariant { base: FaBaseStyle::Thin, is_sharp: false, is_duotone: false } => "thin",
-                    FaVariant { base: FaBaseStyle::Brands, is_sharp: false, is_duotone: false } => "brands",
-
-                    // Sharp variants
-                    FaVariant { base: FaBaseStyle::Solid, is_sharp: true, is_duotone: false } => "sharp-solid",
-                    FaVariant { base: FaBaseStyle::Regular, is_sharp: true, is_duotone: false } => "sharp-regular",
-                    FaVariant { base: FaBaseStyle::Light, is_sharp: true, is_duotone: false } => "sharp-light",
-                    FaVariant { base: FaBaseStyle::Thin, is_sharp: true, is_duotone: false } => "sharp-thin",
-
-                    // Duotone variants
-                    FaVariant { base: FaBaseStyle::Solid, is_sharp: false, is_duotone: true } => "duotone",
-                    FaVariant { base: FaBaseStyle::Regular, is_sharp: false, is_duotone: true } => "duotone-regular",
-                    FaVariant { base: FaBaseStyle::Light, is_sharp: false, is_duotone: true } => "duotone-light",
-                    FaVariant { base: FaBaseStyle::Thin, is_sharp: false, is_duotone: true } => "duotone-thin",
-
-                    // Sharp duotone variants
-                    FaVariant { base: FaBaseStyle::Solid, is_sharp: true, is_duotone: true } => "sharp-duotone-solid",
-                    FaVariant { base: FaBaseStyle::Regular, is_sharp: true, is_duotone: true } => "sharp-duotone-regular",
-                    FaVariant { base: FaBaseStyle::Light, is_sharp: true, is_duotone: true } => "sharp-duotone-light",
-                    FaVariant { base: FaBaseStyle::Thin, is_sharp: true, is_duotone: true } => "sharp-duotone-thin",
-
-                    // Default to solid for any unexpected combinations
-                    _ => "solid",
-                };
-
-                use $crate::process_icon_paths;
-
-                // Find the appropriate icon file by processing each variant
-                process_icon_paths!{self, $name, variant_path, $base_path, [$($variants)*]}
-            }
+        assert_eq!(Icon::Home.name(), "house");
+        assert_eq!(Icon::Home.variants(), Icon::House.variants());
+        assert_ne!(Icon::Home, Icon::House);
+    }
+
+    #[test]
+    fn test_info() {
+        #[fa_icons(path = "../tests/fixtures/svgs", metadata = true, crate = crate)]
+        enum Icon {
+            House,
+            ArrowRight,
+            Github,
         }
+
+        let house = Icon::House.info().unwrap();
+        assert_eq!(house.name, "house");
+        assert_eq!(house.label, "House");
+        assert_eq!(house.unicode, Some('\u{f015}'));
+        assert_eq!(house.aliases, ["home", "home-alt", "home-lg-alt"]);
+        assert_eq!(house.categories, ["Buildings", "Household"]);
+        assert_eq!(
+            house.styles,
+            [
+                FaVariant::default(),
+                FaVariant::new(FaFamily::Classic, FaBaseStyle::Regular)
+            ]
+        );
+        assert_eq!(house.version_added, Some("1.0.0"));
+        assert!(house.matches("ABODE"));
+        assert!(house.matches("home"));
+        assert!(!house.matches("octocat"));
+        assert_eq!(Icon::Github.info().unwrap().styles, [FaVariant::BRANDS]);
+        assert!(Icon::ArrowRight.info().is_none());
+
+        fa_icon_build! {"../tests/fixtures/svgs", enum Plain { House }};
+        assert!(Plain::House.info().is_none());
+        assert!("home".parse::<Plain>().is_err());
     }
-}
 
-#[macro_export]
-macro_rules! extract_enum_variants {
-    // Simple recursive macro to extract identifiers from variant definitions
-    ([], $public:vis, $name:ident) => {
-        #[derive(Debug, Clone, Copy)]
-        $public enum $name {}
-    };
+    #[test]
+    fn test_from_str() {
+        #[fa_icons(path = "../tests/fixtures/svgs", metadata = true, crate = crate)]
+        enum Icon {
+            House,
+            ArrowRight,
+            Github,
+        }
 
-    ([$variant:ident = $path:expr, $($rest:tt)*], $public:vis, $name:ident) => {
-        extract_enum_variants!([$($rest)*], $public, $name, [$variant]);
-    };
+        assert_eq!(Icon::ALL, [Icon::House, Icon::ArrowRight, Icon::Github]);
+        for icon in Icon::ALL {
+            assert_eq!(icon.to_string().parse::<Icon>(), Ok(*icon));
+        }
+        assert_eq!("arrow-right".parse(), Ok(Icon::ArrowRight));
+        assert_eq!("fa-arrow-right".parse(), Ok(Icon::ArrowRight));
+        assert_eq!("home".parse(), Ok(Icon::House));
+        assert_eq!("fa-home".parse(), Ok(Icon::House));
+        assert_eq!(
+            "arrow-left".parse::<Icon>(),
+            Err(FaUnknownIconError {
+                name: "arrow-left".into()
+            })
+        );
+        assert_eq!(Icon::ArrowRight.to_string(), "arrow-right");
+        assert!(Icon::House < Icon::Github);
+        let set: std::collections::HashSet<_> = Icon::ALL.iter().collect();
+        assert_eq!(set.len(), 3);
+    }
 
-    ([$variant:ident, $($rest:tt)*], $public:vis, $name:ident) => {
-        extract_enum_variants!([$($rest)*], $public, $name, [$variant]);
-    };
+    #[test]
+    fn test_fallback() {
+        fa_icon_build! {"../tests/fixtures/svgs", enum Icon { ArrowRight }};
 
-    ([$variant:ident = $path:expr], $public:vis, $name:ident) => {
-        extract_enum_variants!([], $public, $name, [$variant]);
-    };
+        let solid = FaVariant::default();
+        let sharp_light = FaVariant::new(FaFamily::Sharp, FaBaseStyle::Light);
+        assert_eq!(
+            Icon::ArrowRight.resolve_variant(sharp_light, &FaFallback::Auto),
+            Some(solid)
+        );
+        assert_eq!(
+            Icon::ArrowRight.resolve_variant(sharp_light, &FaFallback::Disabled),
+            None
+        );
+    }
 
-    ([$variant:ident], $public:vis, $name:ident) => {
-        extract_enum_variants!([], $public, $name, [$variant]);
-    };
+    #[test]
+    #[cfg(feature = "brands")]
+    fn test_brands() {
+        fa_icon_build! {"../tests/fixtures/svgs", enum Icon { House, Github, }};
 
-    ([], $public:vis, $name:ident, [$($variants:ident),*]) => {
-        #[derive(Debug, Clone, Copy)]
-        $public enum $name {
-            $($variants),*
-        }
-    };
+        let sharp_brands = FaVariant::new(FaFamily::Sharp, FaBaseStyle::Brands);
+        assert!(sharp_brands.validate().is_err());
+        assert_eq!(sharp_brands.dir_name(), None);
+        assert_eq!(Icon::Github.variants(), vec![FaVariant::BRANDS]);
+        assert!(Icon::Github.is_brand());
+        assert!(!Icon::House.is_brand());
+        assert_eq!(
+            Icon::Github.resolve_variant(FaVariant::default(), &FaFallback::Disabled),
+            Some(FaVariant::BRANDS)
+        );
+        assert_eq!(
+            Icon::House.resolve_variant(FaVariant::BRANDS, &FaFallback::Disabled),
+            None
+        );
+    }
 
-    ([$first:ident = $path:expr, $($rest:tt)*], $public:vis, $name:ident, [$($variants:ident),*]) => {
-        extract_enum_variants!([$($rest)*], $public, $name, [$($variants),*, $first]);
-    };
+    #[test]
+    fn test_variant_builders() {
+        fa_icon_build! {"../tests/fixtures/svgs", enum Icon { House }};
 
-    ([$first:ident, $($rest:tt)*], $public:vis, $name:ident, [$($variants:ident),*]) => {
-        extract_enum_variants!([$($rest)*], $public, $name, [$($variants),*, $first]);
-    };
-}
+        assert_eq!(
+            Icon::House.sharp().regular(),
+            FaIconVariant {
+                icon: Icon::House,
+                variant: FaVariant::new(FaFamily::Sharp, FaBaseStyle::Regular),
+            }
+        );
+        assert_eq!(
+            Icon::House.thin().duotone().variant,
+            FaVariant::new(FaFamily::Duotone, FaBaseStyle::Thin)
+        );
+        assert_eq!(Icon::House.brands().variant, FaVariant::BRANDS);
+        let variant = FaVariant::new(FaFamily::SharpDuotone, FaBaseStyle::Light);
+        assert_eq!(Icon::House.with_variant(variant).variant, variant);
+    }
 
-#[macro_export]
-macro_rules! process_icon_paths {
-    // Base case - no more variants to process
-    {$self:expr, $name:ident, $variant_path:expr, $base_path:expr, []} => {
-        // Default fallback
-        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24"></svg>"#
-    };
+    #[test]
+    fn test_embedded_features() {
+        let sharp_light = FaVariant::new(FaFamily::Sharp, FaBaseStyle::Light);
+        assert_eq!(
+            sharp_light.is_embedded(),
+            cfg!(feature = "sharp") && cfg!(feature = "light")
+        );
+        assert_eq!(FaVariant::BRANDS.is_embedded(), cfg!(feature = "brands"));
+        assert!(!FaVariant::new(FaFamily::Sharp, FaBaseStyle::Brands).is_embedded());
 
-    // Process a variant with a custom path
-    {$self:expr, $name:ident, $variant_path:expr, $base_path:expr,
-        [$variant:ident = $path:expr, $($rest:tt)*]
-    } => {
-        match ($self, $variant_path) {
-            ($name::$variant, "solid") => include_str!(concat!($base_path, "/solid/", $path, ".svg")),
-            ($name::$variant, "regular") => include_str!(concat!($base_path, "/regular/", $path, ".svg")),
-            ($name::$variant, "light") => include_str!(concat!($base_path, "/light/", $path, ".svg")),
-            ($name::$variant, "thin") => include_str!(concat!($base_path, "/thin/", $path, ".svg")),
-            ($name::$variant, "duotone") => include_str!(concat!($base_path, "/duotone/", $path, ".svg")),
-            ($name::$variant, "sharp-solid") => include_str!(concat!($base_path, "/sharp-solid/", $path, ".svg")),
-            ($name::$variant, "sharp-regular") => include_str!(concat!($base_path, "/sharp-regular/", $path, ".svg")),
-            ($name::$variant, "sharp-light") => include_str!(concat!($base_path, "/sharp-light/", $path, ".svg")),
-            ($name::$variant, "sharp-thin") => include_str!(concat!($base_path, "/sharp-thin/", $path, ".svg")),
-            ($name::$variant, "sharp-duotone-solid") => include_str!(concat!($base_path, "/sharp-duotone-solid/", $path, ".svg")),
-            ($name::$variant, "sharp-duotone-regular") => include_str!(concat!($base_path, "/sharp-duotone-regular/", $path, ".svg")),
-            ($name::$variant, "sharp-duotone-light") => include_str!(concat!($base_path, "/sharp-duotone-light/", $path, ".svg")),
-            ($name::$variant, "sharp-duotone-thin") => include_str!(concat!($base_path, "/sharp-duotone-thin/", $path, ".svg")),
-            ($name::$variant, "duotone-regular") => include_str!(concat!($base_path, "/duotone-regular/", $path, ".svg")),
-            ($name::$variant, "duotone-light") => include_str!(concat!($base_path, "/duotone-light/", $path, ".svg")),
-            ($name::$variant, "duotone-thin") => include_str!(concat!($base_path, "/duotone-thin/", $path, ".svg")),
-            ($name::$variant, "brands") => include_str!(concat!($base_path, "/solid/", $path, ".svg")),
-            _ => process_icon_paths!{$self, $name, $variant_path, $base_path, [$($rest)*]}
-        }
-    };
+        fa_icon_build! {"../tests/fixtures/svgs", enum Icon { House }};
+        let regular = FaVariant::new(FaFamily::Classic, FaBaseStyle::Regular);
+        assert_eq!(Icon::House.has_variant(regular), cfg!(feature = "regular"));
+    }
+
+    #[test]
+    #[cfg(feature = "free")]
+    fn test_free() {
+        let regular = FaVariant::new(FaFamily::Classic, FaBaseStyle::Regular);
+        assert!(free::Icon::House.has_variant(regular));
+        assert_eq!(free::Icon::Github.variants(), vec![FaVariant::BRANDS]);
+    }
+
+    #[test]
+    fn test_style_snapshot() {
+        let red = Color::from_rgba8(255, 0, 0, 255);
+        let style = FaStyle::default().light().sharp().color(red);
+        let snapshot = style.snapshot();
+        assert_eq!(
+            snapshot,
+            FaStyleSnapshot {
+                variant: FaVariant::new(FaFamily::Sharp, FaBaseStyle::Light),
+                color: Some(red),
+                primary: None,
+                secondary: None,
+            }
+        );
+        assert_eq!(FaStyle::from(snapshot).snapshot(), snapshot);
+    }
+
+    #[test]
+    fn test_style_setters() {
+        let variant = |style: FaStyle| style.snapshot().variant;
+        let parent = FaStyle::default().sharp().duotone().light();
+        assert_eq!(
+            variant(parent.clone().not_sharp()),
+            FaVariant::new(FaFamily::Duotone, FaBaseStyle::Light)
+        );
+        assert_eq!(
+            variant(parent.clone().not_duotone()),
+            FaVariant::new(FaFamily::Sharp, FaBaseStyle::Light)
+        );
+        assert_eq!(
+            variant(parent.clone().family(FaFamily::Classic)),
+            FaVariant::new(FaFamily::Classic, FaBaseStyle::Light)
+        );
+        assert_eq!(
+            variant(parent.variant(FaVariant::BRANDS)),
+            FaVariant::BRANDS
+        );
+
+        assert!(FaStyle::default().brands().validate().is_ok());
+        assert_eq!(
+            FaStyle::default().brands().duotone().validate(),
+            Err(FaVariantError::BrandsModifier(FaVariant::new(
+                FaFamily::Duotone,
+                FaBaseStyle::Brands
+            )))
+        );
+        assert_eq!("fa-sharp-duotone".parse(), Ok(FaFamily::SharpDuotone));
+        assert_eq!(FaFamily::Duotone.to_string(), "duotone");
+    }
+
+    #[test]
+    fn test_duotone_opacity() {
+        let css = |style: FaStyle| {
+            let style = Style::from(style);
+            let mut ext = FaStyleExt::default();
+            ext.read_explicit(&style, &style, &std::time::Instant::now(), &mut false);
+            ext.css_for(FaVariant::new(FaFamily::Duotone, FaBaseStyle::Solid))
+                .split_whitespace()
+                .collect::<String>()
+        };
+        assert!(
+            css(FaStyle::default())
+                .contains(".fa-secondary{fill:currentColor!important;opacity:0.4!important;}")
+        );
+        let swapped = css(FaStyle::default().primary_opacity(0.8).swap_opacity());
+        assert!(
+            swapped.contains(".fa-primary{fill:currentColor!important;opacity:0.4!important;}")
+        );
+        assert!(
+            swapped.contains(".fa-secondary{fill:currentColor!important;opacity:0.8!important;}")
+        );
+    }
+
+    #[test]
+    fn test_style_cascade() {
+        let variant = |parent: FaStyle, child: FaStyle| {
+            let style = Style::from(parent).apply(Style::from(child));
+            let mut ext = FaStyleExt::default();
+            ext.read_explicit(&style, &style, &std::time::Instant::now(), &mut false);
+            ext.variant()
+        };
+        assert_eq!(
+            variant(
+                FaStyle::default().sharp().light(),
+                FaStyle::default().not_sharp()
+            ),
+            FaVariant::new(FaFamily::Classic, FaBaseStyle::Light)
+        );
+        assert_eq!(
+            variant(
+                FaStyle::default().family(FaFamily::Jelly).regular(),
+                FaStyle::default().duotone()
+            ),
+            FaVariant::new(FaFamily::JellyDuo, FaBaseStyle::Regular)
+        );
+        assert_eq!(
+            variant(FaStyle::default().sharp(), FaStyle::default().thin()),
+            FaVariant::new(FaFamily::Sharp, FaBaseStyle::Thin)
+        );
+        assert_eq!(
+            variant(
+                FaStyle::default().duotone(),
+                FaStyle::default().family(FaFamily::Slab)
+            ),
+            FaVariant::new(FaFamily::Slab, FaBaseStyle::Solid)
+        );
+    }
+
+    #[test]
+    fn test_icon_box() {
+        let unstyled = IconBox::new(14.0, None, 0.875, false, false);
+        assert_eq!(
+            unstyled,
+            IconBox {
+                width: 12.25,
+                height: 14.0,
+                offset: 0.0
+            }
+        );
+        let styled = IconBox::new(16.0, Some(FaSize::TwoXl), 0.875, true, true);
+        assert_eq!(
+            styled,
+            IconBox {
+                width: 40.0,
+                height: 32.0,
+                offset: 4.0
+            }
+        );
+    }
+
+    #[test]
+    fn test_update_icon() {
+        fa_icon_build! {"../tests/fixtures/svgs", enum Icon { House, ArrowRight, Github }};
+
+        let mut view = fa_icon(Icon::Github);
+        assert_eq!(view.variant, FaVariant::BRANDS);
+        assert_eq!(view.aspect_ratio.get_untracked(), 496.0 / 512.0);
+
+        view.set_icon(Box::new(Icon::ArrowRight));
+        assert_eq!(view.icon, Icon::ArrowRight);
+        assert_eq!(view.variant, FaVariant::default());
+        assert_eq!(view.aspect_ratio.get_untracked(), 448.0 / 512.0);
+        assert_eq!(view.icon_box.width, 448.0 / 512.0 * f64::from(DEFAULT_FONT_SIZE));
 
-    // Process a regular variant (no custom path)
-    {$self:expr, $name:ident, $variant_path:expr, $base_path:expr,
-        [$variant:ident, $($rest:tt)*]
-    } => {
-        match ($self, $variant_path) {
-            ($name::$variant, "solid") => include_str!(concat!($base_path, "/solid/", stringify!($variant), ".svg")),
-            ($name::$variant, "regular") => include_str!(concat!($base_path, "/regular/", stringify!($variant), ".svg")),
-            ($name::$variant, "light") => include_str!(concat!($base_path, "/light/", stringify!($variant), ".svg")),
-            ($name::$variant, "thin") => include_str!(concat!($base_path, "/thin/", stringify!($variant), ".svg")),
-            ($name::$variant, "duotone") => include_str!(concat!($base_path, "/duotone/", stringify!($variant), ".svg")),
-            ($name::$variant, "sharp-solid") => include_str!(concat!($base_path, "/sharp-solid/", stringify!($variant), ".svg")),
-            ($name::$variant, "sharp-regular") => include_str!(concat!($base_path, "/sharp-regular/", stringify!($variant), ".svg")),
-            ($name::$variant, "sharp-light") => include_str!(concat!($base_path, "/sharp-light/", stringify!($variant), ".svg")),
-            ($name::$variant, "sharp-thin") => include_str!(concat!($base_path, "/sharp-thin/", stringify!($variant), ".svg")),
-            ($name::$variant, "sharp-duotone-solid") => include_str!(concat!($base_path, "/sharp-duotone-solid/", stringify!($variant), ".svg")),
-            ($name::$variant, "sharp-duotone-regular") => include_str!(concat!($base_path, "/sharp-duotone-regular/", stringify!($variant), ".svg")),
-            ($name::$variant, "sharp-duotone-light") => include_str!(concat!($base_path, "/sharp-duotone-light/", stringify!($variant), ".svg")),
-            ($name::$variant, "sharp-duotone-thin") => include_str!(concat!($base_path, "/sharp-duotone-thin/", stringify!($variant), ".svg")),
-            ($name::$variant, "duotone-regular") => include_str!(concat!($base_path, "/duotone-regular/", stringify!($variant), ".svg")),
-            ($name::$variant, "duotone-light") => include_str!(concat!($base_path, "/duotone-light/", stringify!($variant), ".svg")),
-            ($name::$variant, "duotone-thin") => include_str!(concat!($base_path, "/duotone-thin/", stringify!($variant), ".svg")),
-            ($name::$variant, "brands") => include_str!(concat!($base_path, "/solid/", stringify!($variant), ".svg")),
-            _ => process_icon_paths!{$self, $name, $variant_path, $base_path, [$($rest)*]}
+        view.set_icon(Box::new(Icon::House));
+        assert_eq!(view.aspect_ratio.get_untracked(), 576.0 / 512.0);
+
+        view.set_icon(Box::new("arrow-right"));
+        assert_eq!(view.icon, Icon::House);
+        assert_eq!(view.aspect_ratio.get_untracked(), 576.0 / 512.0);
+    }
+
+    #[test]
+    fn test_variant_dirs() {
+        use floem_fontawesome_codegen::{VARIANT_DIRS, VARIANTS, variant_dir};
+
+        assert_eq!(FaVariant::ALL.len(), VARIANTS.len());
+        for (index, &variant) in FaVariant::ALL.iter().enumerate() {
+            let (family, style) = VARIANTS[index];
+            assert_eq!(variant.family.name(), family);
+            assert_eq!(variant.base.to_string(), style);
+            assert_eq!(variant.index(), Some(index));
+            assert_eq!(variant.dir_name().as_ref(), Some(&VARIANT_DIRS[index]));
+            assert_eq!(variant.to_string(), variant_dir(family, style));
+            assert_eq!(VARIANT_DIRS[index].parse(), Ok(variant));
+            assert_eq!(format!("{family}/{style}").parse(), Ok(variant));
         }
-    };
+        assert_eq!(FaFamily::ALL.len(), 18);
+        for family in FaFamily::ALL {
+            assert_eq!(family.name().parse(), Ok(*family));
+        }
+    }
 
-    // Handle the last variant with a custom path (no comma at the end)
-    {$self:expr, $name:ident, $variant_path:expr, $base_path:expr,
-        [$variant:ident = $path:expr]
-    } => {
-        match ($self, $variant_path) {
-            ($name::$variant, "solid") => include_str!(concat!($base_path, "/solid/", $path, ".svg")),
-            ($name::$variant, "regular") => include_str!(concat!($base_path, "/regular/", $path, ".svg")),
-            ($name::$variant, "light") => include_str!(concat!($base_path, "/light/", $path, ".svg")),
-            ($name::$variant, "thin") => include_str!(concat!($base_path, "/thin/", $path, ".svg")),
-            ($name::$variant, "duotone") => include_str!(concat!($base_path, "/duotone/", $path, ".svg")),
-            ($name::$variant, "sharp-solid") => include_str!(concat!($base_path, "/sharp-solid/", $path, ".svg")),
-            ($name::$variant, "sharp-regular") => include_str!(concat!($base_path, "/sharp-regular/", $path, ".svg")),
-            ($name::$variant, "sharp-light") => include_str!(concat!($base_path, "/sharp-light/", $path, ".svg")),
-            ($name::$variant, "sharp-thin") => include_str!(concat!($base_path, "/sharp-thin/", $path, ".svg")),
-            ($name::$variant, "sharp-duotone-solid") => include_str!(concat!($base_path, "/sharp-duotone-solid/", $path, ".svg")),
-            ($name::$variant, "sharp-duotone-regular") => include_str!(concat!($base_path, "/sharp-duotone-regular/", $path, ".svg")),
-            ($name::$variant, "sharp-duotone-light") => include_str!(concat!($base_path, "/sharp-duotone-light/", $path, ".svg")),
-            ($name::$variant, "sharp-duotone-thin") => include_str!(concat!($base_path, "/sharp-duotone-thin/", $path, ".svg")),
-            ($name::$variant, "duotone-regular") => include_str!(concat!($base_path, "/duotone-regular/", $path, ".svg")),
-            ($name::$variant, "duotone-light") => include_str!(concat!($base_path, "/duotone-light/", $path, ".svg")),
-            ($name::$variant, "duotone-thin") => include_str!(concat!($base_path, "/duotone-thin/", $path, ".svg")),
-            ($name::$variant, "brands") => include_str!(concat!($base_path, "/solid/", $path, ".svg")),
-            _ => r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24"></svg>"#
+    #[test]
+    fn test_parse_variant() {
+        let duotone = FaVariant::new(FaFamily::Duotone, FaBaseStyle::Solid);
+        let sharp_duotone_thin = FaVariant::new(FaFamily::SharpDuotone, FaBaseStyle::Thin);
+        assert_eq!(duotone.to_string(), "duotone");
+        for name in [
+            "duotone",
+            "duotone-solid",
+            "duotone/solid",
+            "fa-duotone",
+            "fad",
+        ] {
+            assert_eq!(name.parse(), Ok(duotone), "{name}");
         }
-    };
+        for name in [
+            "sharp-duotone-thin",
+            "sharp-duotone/thin",
+            "fa-sharp-duotone fa-thin",
+            "fa-sharp fa-duotone fa-thin",
+        ] {
+            assert_eq!(name.parse(), Ok(sharp_duotone_thin), "{name}");
+        }
+        assert_eq!(
+            "classic/regular".parse(),
+            Ok(FaVariant::new(FaFamily::Classic, FaBaseStyle::Regular))
+        );
+        assert_eq!(
+            "fasr".parse(),
+            Ok(FaVariant::new(FaFamily::Sharp, FaBaseStyle::Regular))
+        );
+        assert_eq!("brands".parse(), Ok(FaVariant::BRANDS));
+        assert!("sharp-brands".parse::<FaVariant>().is_err());
+        assert!("fa-sharp fa-house".parse::<FaVariant>().is_err());
+        assert!("chisel/solid".parse::<FaVariant>().is_err());
 
-    // Handle the last regular variant (no comma at the end)
-    {$self:expr, $name:ident, $variant_path:expr, $base_path:expr,
-        [$variant:ident]
-    } => {
-        match ($self, $variant_path) {
-            ($name::$variant, "solid") => include_str!(concat!($base_path, "/solid/", stringify!($variant), ".svg")),
-            ($name::$variant, "regular") => include_str!(concat!($base_path, "/regular/", stringify!($variant), ".svg")),
-            ($name::$variant, "light") => include_str!(concat!($base_path, "/light/", stringify!($variant), ".svg")),
-            ($name::$variant, "thin") => include_str!(concat!($base_path, "/thin/", stringify!($variant), ".svg")),
-            ($name::$variant, "duotone") => include_str!(concat!($base_path, "/duotone/", stringify!($variant), ".svg")),
-            ($name::$variant, "sharp-solid") => include_str!(concat!($base_path, "/sharp-solid/", stringify!($variant), ".svg")),
-            ($name::$variant, "sharp-regular") => include_str!(concat!($base_path, "/sharp-regular/", stringify!($variant), ".svg")),
-            ($name::$variant, "sharp-light") => include_str!(concat!($base_path, "/sharp-light/", stringify!($variant), ".svg")),
-            ($name::$variant, "sharp-thin") => include_str!(concat!($base_path, "/sharp-thin/", stringify!($variant), ".svg")),
-            ($name::$variant, "sharp-duotone-solid") => include_str!(concat!($base_path, "/sharp-duotone-solid/", stringify!($variant), ".svg")),
-            ($name::$variant, "sharp-duotone-regular") => include_str!(concat!($base_path, "/sharp-duotone-regular/", stringify!($variant), ".svg")),
-            ($name::$variant, "sharp-duotone-light") => include_str!(concat!($base_path, "/sharp-duotone-light/", stringify!($variant), ".svg")),
-            ($name::$variant, "sharp-duotone-thin") => include_str!(concat!($base_path, "/sharp-duotone-thin/", stringify!($variant), ".svg")),
-            ($name::$variant, "duotone-regular") => include_str!(concat!($base_path, "/duotone-regular/", stringify!($variant), ".svg")),
-            ($name::$variant, "duotone-light") => include_str!(concat!($base_path, "/duotone-light/", stringify!($variant), ".svg")),
-            ($name::$variant, "duotone-thin") => include_str!(concat!($base_path, "/duotone-thin/", stringify!($variant), ".svg")),
-            ($name::$variant, "brands") => include_str!(concat!($base_path, "/solid/", stringify!($variant), ".svg")),
-            _ => r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24"></svg>"#
+        let jelly_duo = FaVariant::new(FaFamily::JellyDuo, FaBaseStyle::Regular);
+        for name in [
+            "jelly-duo",
+            "jelly-duo-regular",
+            "fa-jelly fa-duotone fa-regular",
+        ] {
+            assert_eq!(name.parse(), Ok(jelly_duo), "{name}");
         }
-    };
-}
+        assert_eq!(
+            "fa-utility fa-semibold".parse(),
+            Ok(FaVariant::new(FaFamily::Utility, FaBaseStyle::Semibold))
+        );
+        let chisel_thin = FaVariant::new(FaFamily::Chisel, FaBaseStyle::Thin);
+        assert_eq!(
+            chisel_thin.validate(),
+            Err(FaVariantError::Unavailable(chisel_thin))
+        );
+        assert_eq!(chisel_thin.to_string(), "chisel-thin");
 
-#[cfg(test)]
-mod tests {
-    #[test]
-    fn test_macros() {
-        fa_icon_build! {"", enum Icon {}};
+        assert_eq!("fa-light".parse(), Ok(FaBaseStyle::Light));
+        assert_eq!(FaBaseStyle::Thin.to_string(), "thin");
+        assert!("duotone".parse::<FaBaseStyle>().is_err());
+        assert_eq!(
+            FaVariant::new(FaFamily::Sharp, FaBaseStyle::Brands).to_string(),
+            "sharp-brands"
+        );
     }
 }
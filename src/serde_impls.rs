@@ -0,0 +1,198 @@
+//! `serde` support, enabled by the `serde` feature.
+//!
+//! Variants and icons are written as the strings Font Awesome uses for them (`sharp-light`,
+//! `arrow-right`) rather than as their Rust structure, so saved settings stay readable.
+
+/// Implements `Serialize` and `Deserialize` for an icon enum through its Font Awesome name.
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __fa_serde {
+    ($name:ident) => {
+        impl $crate::__serde::Serialize for $name {
+            fn serialize<S: $crate::__serde::Serializer>(
+                &self,
+                serializer: S,
+            ) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str($crate::FaIconTrait::name(self))
+            }
+        }
+
+        impl<'de> $crate::__serde::Deserialize<'de> for $name {
+            fn deserialize<D: $crate::__serde::Deserializer<'de>>(
+                deserializer: D,
+            ) -> Result<Self, D::Error> {
+                $crate::__fa_deserialize_str(deserializer)
+            }
+        }
+    };
+}
+
+/// Without the `serde` feature icon enums don't implement serde's traits.
+#[cfg(not(feature = "serde"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __fa_serde {
+    ($name:ident) => {};
+}
+
+#[cfg(feature = "serde")]
+mod imp {
+    use std::borrow::Cow;
+    use std::fmt::Display;
+    use std::str::FromStr;
+
+    use floem::peniko::Color;
+    use floem::peniko::color::Rgba8;
+    use serde::de::Error as _;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::{FaBaseStyle, FaFamily, FaVariant};
+
+    /// Deserializes a string and parses it with `FromStr`.
+    #[doc(hidden)]
+    pub fn __fa_deserialize_str<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: FromStr,
+        T::Err: Display,
+    {
+        let name = Cow::<str>::deserialize(deserializer)?;
+        name.parse().map_err(D::Error::custom)
+    }
+
+    impl Serialize for FaVariant {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_str(self)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for FaVariant {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            __fa_deserialize_str(deserializer)
+        }
+    }
+
+    impl Serialize for FaBaseStyle {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_str(self)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for FaBaseStyle {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            __fa_deserialize_str(deserializer)
+        }
+    }
+
+    impl Serialize for FaFamily {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_str(self)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for FaFamily {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            __fa_deserialize_str(deserializer)
+        }
+    }
+
+    /// Optional colors as `#rrggbbaa` strings.
+    pub(crate) mod color {
+        use super::*;
+
+        pub(crate) fn serialize<S: Serializer>(
+            color: &Option<Color>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            color
+                .map(|color| {
+                    let Rgba8 { r, g, b, a } = color.to_rgba8();
+                    format!("#{r:02x}{g:02x}{b:02x}{a:02x}")
+                })
+                .serialize(serializer)
+        }
+
+        pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Option<Color>, D::Error> {
+            let Some(hex) = Option::<Cow<str>>::deserialize(deserializer)? else {
+                return Ok(None);
+            };
+            parse_hex(&hex)
+                .map(Some)
+                .ok_or_else(|| D::Error::custom(format!("invalid color `{hex}`")))
+        }
+
+        /// Parses `#rrggbb` or `#rrggbbaa`.
+        pub(crate) fn parse_hex(hex: &str) -> Option<Color> {
+            let digits = hex.strip_prefix('#')?;
+            if !matches!(digits.len(), 6 | 8) || !digits.is_ascii() {
+                return None;
+            }
+            let channel = |i: usize| u8::from_str_radix(digits.get(i..i + 2)?, 16).ok();
+            let alpha = if digits.len() == 8 { channel(6)? } else { 255 };
+            Some(Color::from_rgba8(
+                channel(0)?,
+                channel(2)?,
+                channel(4)?,
+                alpha,
+            ))
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+pub use imp::__fa_deserialize_str;
+#[cfg(feature = "serde")]
+pub(crate) use imp::color;
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use serde::Deserialize;
+    use serde::de::IntoDeserializer;
+    use serde::de::value::{Error, StrDeserializer};
+
+    use crate::{FaBaseStyle, FaFamily, FaVariant, fa_icons};
+
+    #[fa_icons(path = "../tests/fixtures/svgs", metadata = true, crate = crate)]
+    enum Icon {
+        House,
+        ArrowRight,
+    }
+
+    fn from_str<'de, T: Deserialize<'de>>(value: &'de str) -> Result<T, Error> {
+        let deserializer: StrDeserializer<Error> = value.into_deserializer();
+        T::deserialize(deserializer)
+    }
+
+    #[test]
+    fn test_deserialize() {
+        assert_eq!(
+            from_str::<FaVariant>("sharp-light"),
+            Ok(FaVariant::new(FaFamily::Sharp, FaBaseStyle::Light))
+        );
+        assert_eq!(from_str::<FaBaseStyle>("thin"), Ok(FaBaseStyle::Thin));
+        assert_eq!(from_str::<Icon>("arrow-right"), Ok(Icon::ArrowRight));
+        assert_eq!(from_str::<Icon>("home"), Ok(Icon::House));
+        assert!(from_str::<Icon>("arrow-left").is_err());
+    }
+
+    #[test]
+    fn test_parse_hex() {
+        use floem::peniko::Color;
+
+        use super::imp::color::parse_hex;
+
+        assert_eq!(
+            parse_hex("#ff000080"),
+            Some(Color::from_rgba8(255, 0, 0, 128))
+        );
+        assert_eq!(
+            parse_hex("#00ff00"),
+            Some(Color::from_rgba8(0, 255, 0, 255))
+        );
+        assert_eq!(parse_hex("00ff00"), None);
+        assert_eq!(parse_hex("#00ff0"), None);
+    }
+}
@@ -0,0 +1,159 @@
+use floem::style::StylePropValue;
+
+/// Font Awesome's sizing scale, relative to the font size the icon inherits.
+///
+/// The relative sizes match Font Awesome's CSS: `2xs` is 0.625em, `xs` 0.75em, `sm`
+/// 0.875em, `lg` 1.25em, `xl` 1.5em and `2xl` 2em. `Times(n)` is `fa-<n>x`, n times the
+/// font size, for n from 1 to 10; build it with [`FaSize::times`] to check the range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FaSize {
+    TwoXs,
+    Xs,
+    Sm,
+    Lg,
+    Xl,
+    TwoXl,
+    Times(u8),
+}
+
+impl FaSize {
+    /// The relative sizes from smallest to largest, followed by `1x` to `10x`.
+    pub const ALL: [FaSize; 16] = [
+        FaSize::TwoXs,
+        FaSize::Xs,
+        FaSize::Sm,
+        FaSize::Lg,
+        FaSize::Xl,
+        FaSize::TwoXl,
+        FaSize::Times(1),
+        FaSize::Times(2),
+        FaSize::Times(3),
+        FaSize::Times(4),
+        FaSize::Times(5),
+        FaSize::Times(6),
+        FaSize::Times(7),
+        FaSize::Times(8),
+        FaSize::Times(9),
+        FaSize::Times(10),
+    ];
+
+    /// `fa-<n>x`, or `None` when `n` is outside Font Awesome's 1 to 10.
+    pub fn times(n: u8) -> Option<FaSize> {
+        (1..=10).contains(&n).then_some(FaSize::Times(n))
+    }
+
+    /// The icon size in ems. `Times` outside 1 to 10 is clamped into that range.
+    pub fn scale(&self) -> f64 {
+        match self {
+            FaSize::TwoXs => 0.625,
+            FaSize::Xs => 0.75,
+            FaSize::Sm => 0.875,
+            FaSize::Lg => 1.25,
+            FaSize::Xl => 1.5,
+            FaSize::TwoXl => 2.0,
+            FaSize::Times(times) => f64::from((*times).clamp(1, 10)),
+        }
+    }
+}
+
+impl StylePropValue for FaSize {}
+
+impl std::fmt::Display for FaSize {
+    /// Writes the class suffix, e.g. `2xs` or `3x`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FaSize::TwoXs => f.write_str("2xs"),
+            FaSize::Xs => f.write_str("xs"),
+            FaSize::Sm => f.write_str("sm"),
+            FaSize::Lg => f.write_str("lg"),
+            FaSize::Xl => f.write_str("xl"),
+            FaSize::TwoXl => f.write_str("2xl"),
+            FaSize::Times(times) => write!(f, "{times}x"),
+        }
+    }
+}
+
+impl std::str::FromStr for FaSize {
+    type Err = FaUnknownSizeError;
+
+    /// Parses a size such as `lg` or `3x`, optionally as a CSS class (`fa-lg`).
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        let trimmed = name.trim();
+        let size = trimmed.strip_prefix("fa-").unwrap_or(trimmed);
+        FaSize::ALL
+            .into_iter()
+            .find(|candidate| candidate.to_string() == size)
+            .ok_or_else(|| FaUnknownSizeError {
+                name: name.to_string(),
+            })
+    }
+}
+
+/// A string that doesn't name a [`FaSize`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FaUnknownSizeError {
+    pub name: String,
+}
+
+impl std::fmt::Display for FaUnknownSizeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unknown Font Awesome size `{}`", self.name)
+    }
+}
+
+impl std::error::Error for FaUnknownSizeError {}
+
+/// Width over height of an svg's `viewBox`, e.g. 0.875 for Font Awesome's 448 by 512
+/// icons.
+pub(crate) fn aspect_ratio(svg: &str) -> Option<f64> {
+    let start = svg.find("viewBox=")? + "viewBox=".len();
+    let quote = svg[start..].chars().next()?;
+    let rest = &svg[start + quote.len_utf8()..];
+    let view_box = &rest[..rest.find(quote)?];
+    let mut numbers = view_box
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|number| !number.is_empty())
+        .map(str::parse::<f64>);
+    let (Some(Ok(_)), Some(Ok(_)), Some(Ok(width)), Some(Ok(height))) = (
+        numbers.next(),
+        numbers.next(),
+        numbers.next(),
+        numbers.next(),
+    ) else {
+        return None;
+    };
+    (width > 0.0 && height > 0.0).then_some(width / height)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_size() {
+        assert_eq!("2xs".parse(), Ok(FaSize::TwoXs));
+        assert_eq!("fa-lg".parse(), Ok(FaSize::Lg));
+        assert_eq!("10x".parse(), Ok(FaSize::Times(10)));
+        assert!("11x".parse::<FaSize>().is_err());
+        assert!("0x".parse::<FaSize>().is_err());
+        for size in FaSize::ALL {
+            assert_eq!(size.to_string().parse(), Ok(size));
+        }
+        assert_eq!(FaSize::Sm.scale(), 0.875);
+        assert_eq!(FaSize::Times(3).scale(), 3.0);
+        assert_eq!(FaSize::times(3), Some(FaSize::Times(3)));
+        assert_eq!(FaSize::times(0), None);
+        assert_eq!(FaSize::times(11), None);
+        assert_eq!(FaSize::Times(0).scale(), 1.0);
+        assert_eq!(FaSize::Times(200).scale(), 10.0);
+    }
+
+    #[test]
+    fn test_aspect_ratio() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 448 512"><path/></svg>"#;
+        assert_eq!(aspect_ratio(svg), Some(0.875));
+        assert_eq!(aspect_ratio("<svg viewBox='0,0,640,512'/>"), Some(1.25));
+        assert_eq!(aspect_ratio("<svg viewBox=\"0 0 0 512\"/>"), None);
+        assert_eq!(aspect_ratio("<svg width=\"16\"/>"), None);
+    }
+}